@@ -1,18 +1,22 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
 use std::fs;
-use std::io::{BufRead, BufReader};
+use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::process::{Child, Command, Stdio};
+use std::process::Stdio;
 use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::{Duration, Instant};
 use sysinfo::System;
 use tauri::{
     menu::{Menu, MenuItem, PredefinedMenuItem},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
     AppHandle, Emitter, Listener, Manager, State,
 };
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, Command};
 
 // ───────────────────────────── Data Structures ──────────────────────────────
 
@@ -183,6 +187,49 @@ impl Default for TranscriberModel {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct ProxyConfig {
+    #[serde(default)]
+    enabled: bool,
+    /// "http" 或 "socks5"
+    #[serde(default = "default_proxy_scheme")]
+    scheme: String,
+    #[serde(default)]
+    host: String,
+    #[serde(default)]
+    port: u16,
+    /// "user:password" 形式，留空表示不需要认证
+    #[serde(default)]
+    auth: String,
+}
+
+fn default_proxy_scheme() -> String {
+    "http".to_string()
+}
+
+impl Default for ProxyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            scheme: default_proxy_scheme(),
+            host: String::new(),
+            port: 0,
+            auth: String::new(),
+        }
+    }
+}
+
+impl ProxyConfig {
+    /// 拼成 `ureq::Proxy::new` 能解析的 `scheme://[auth@]host:port` 形式
+    fn to_proxy_url(&self) -> String {
+        if self.auth.is_empty() {
+            format!("{}://{}:{}", self.scheme, self.host, self.port)
+        } else {
+            format!("{}://{}@{}:{}", self.scheme, self.auth, self.host, self.port)
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 struct Config {
     api_keys: ApiKeys,
@@ -190,6 +237,12 @@ struct Config {
     models: Vec<Model>,
     #[serde(default)]
     transcriber_models: Vec<TranscriberModel>,
+    #[serde(default)]
+    proxy: ProxyConfig,
+    /// 用户可自定义的网络可达性检查目标；内置的 "Local Services" 分组由
+    /// Node/Python 后端的探活端口在运行时派生，不落盘在这个列表里
+    #[serde(default = "default_network_check_targets")]
+    network_check_targets: Vec<NetworkCheckTarget>,
 }
 
 impl Default for Config {
@@ -207,10 +260,75 @@ impl Default for Config {
             media_directories: vec![],
             models: vec![],
             transcriber_models: vec![],
+            proxy: ProxyConfig::default(),
+            network_check_targets: default_network_check_targets(),
         }
     }
 }
 
+/// 单个网络可达性检查目标；`network_check_targets` 里持久化的都是这种
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct NetworkCheckTarget {
+    url: String,
+    /// 分区标签，相同 group 的目标在诊断面板里聚在一起展示
+    #[serde(default = "default_network_check_group")]
+    group: String,
+    /// 是否预期该目标可达；只随配置持久化，`run_network_checks` 不读取它，
+    /// `NetworkCheckUpdate` 也不携带这个字段
+    #[serde(default = "default_expected_reachable")]
+    expected_reachable: bool,
+}
+
+fn default_network_check_group() -> String {
+    "Upstream Sites".to_string()
+}
+
+fn default_expected_reachable() -> bool {
+    true
+}
+
+const NETWORK_CHECK_SITES: &[&str] = &[
+    "https://musicbrainz.org/",
+    "https://music.163.com/",
+    "https://www.themoviedb.org/",
+    "https://www.javbus.com/",
+    "https://www.jav321.com",
+    "https://javdb.com",
+    "https://www.dmm.co.jp",
+    "https://ads.contents.fc2.com",
+    "https://chii.in",
+    "https://www.getchu.com",
+    "https://hanime1.me",
+];
+
+fn default_network_check_targets() -> Vec<NetworkCheckTarget> {
+    NETWORK_CHECK_SITES
+        .iter()
+        .map(|url| NetworkCheckTarget {
+            url: url.to_string(),
+            group: default_network_check_group(),
+            expected_reachable: true,
+        })
+        .collect()
+}
+
+/// 用户在 `config.network_check_targets` 里自定义的目标，加上一个从 Node/Python
+/// 探活端口派生的 "Local Services" 分组——后者不落盘，每次都按当前配置现算
+fn network_check_targets(config: &Config) -> Vec<NetworkCheckTarget> {
+    let mut targets = config.network_check_targets.clone();
+    targets.push(NetworkCheckTarget {
+        url: format!("http://{}:{}", NODE_READINESS.0, NODE_READINESS.1),
+        group: "Local Services".to_string(),
+        expected_reachable: true,
+    });
+    targets.push(NetworkCheckTarget {
+        url: format!("http://{}:{}", PYTHON_READINESS.0, PYTHON_READINESS.1),
+        group: "Local Services".to_string(),
+        expected_reachable: true,
+    });
+    targets
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 struct LauncherSettings {
     #[serde(default)]
@@ -219,8 +337,74 @@ struct LauncherSettings {
     auto_start_python: bool,
     #[serde(default)]
     start_minimized: bool,
+    /// 是否注册到 OS 登录项开机自启（Windows Run 键/macOS LaunchAgents/Linux
+    /// autostart .desktop），由 `set_launch_on_startup` 通过 `auto-launch` crate 落地
+    #[serde(default)]
+    launch_on_startup: bool,
+    /// 进程异常退出后是否自动重启
+    #[serde(default = "default_restart_on_crash")]
+    restart_on_crash: bool,
+    /// 连续重启多少次后放弃并把状态标记为 Crashed
+    #[serde(default = "default_max_restarts")]
+    max_restarts: u32,
+    /// 重启退避的基准时长（毫秒），第 N 次重启等待 `backoff_base_ms * 2^(N-1)`，
+    /// 封顶 `MAX_RESTART_BACKOFF`
+    #[serde(default = "default_backoff_base_ms")]
+    backoff_base_ms: u64,
+    /// 后台（尤其是 `start_minimized` 时）是否用系统通知提醒进程崩溃/放弃重启
+    #[serde(default)]
+    notify_on_events: bool,
+    /// 监听 config.json/server.js/subtitle_process_backend.py，变化后自动热重启
+    #[serde(default)]
+    watch_and_reload: bool,
+    /// 每个服务最多保留几个滚动日志备份，传给 `RotatingLogFile`；0 表示不保留备份，
+    /// 达到单文件大小上限就直接清空重写
+    #[serde(default = "default_log_retention")]
+    log_retention: u32,
+    /// 隧道客户端可执行文件路径（如打包的 `frpc`），留空则无法启动
+    #[serde(default)]
+    tunnel_client_path: String,
+    /// 隧道中转服务器地址，形如 `host:port`
+    #[serde(default)]
+    tunnel_server_addr: String,
+    /// 隧道鉴权令牌，透传给隧道客户端
+    #[serde(default)]
+    tunnel_token: String,
+    /// 隧道子域名/自定义名称，留空则由服务端分配
+    #[serde(default)]
+    tunnel_subdomain: String,
+    /// 隧道期望的远端端口，0 表示由服务端随机分配
+    #[serde(default)]
+    tunnel_remote_port: u16,
+    /// 托盘菜单/提示气泡/消息框使用的语言代码（"zh-Hans"/"en"/"ja"），由 `tr()` 查表
+    #[serde(default = "default_language")]
+    language: String,
+    /// 是否启用 Sentry 崩溃上报；默认关闭，用户需要显式开启
+    #[serde(default)]
+    telemetry_enabled: bool,
+    /// Sentry 项目的 DSN；留空即使 `telemetry_enabled` 为 true 也不会上报
     #[serde(default)]
-    auto_start_on_boot: bool,
+    telemetry_dsn: String,
+}
+
+fn default_language() -> String {
+    "zh-Hans".to_string()
+}
+
+fn default_restart_on_crash() -> bool {
+    true
+}
+
+fn default_max_restarts() -> u32 {
+    5
+}
+
+fn default_backoff_base_ms() -> u64 {
+    1000
+}
+
+fn default_log_retention() -> u32 {
+    5
 }
 
 impl Default for LauncherSettings {
@@ -229,22 +413,243 @@ impl Default for LauncherSettings {
             auto_start_node: false,
             auto_start_python: false,
             start_minimized: false,
-            auto_start_on_boot: false,
+            launch_on_startup: false,
+            restart_on_crash: default_restart_on_crash(),
+            max_restarts: default_max_restarts(),
+            backoff_base_ms: default_backoff_base_ms(),
+            notify_on_events: false,
+            watch_and_reload: false,
+            log_retention: default_log_retention(),
+            tunnel_client_path: String::new(),
+            tunnel_server_addr: String::new(),
+            tunnel_token: String::new(),
+            tunnel_subdomain: String::new(),
+            tunnel_remote_port: 0,
+            language: default_language(),
+            telemetry_enabled: false,
+            telemetry_dsn: String::new(),
         }
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct WindowGeometry {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+    maximized: bool,
+    fullscreen: bool,
+}
+
+/// 进程优雅关闭：发出终止信号后等待退出的宽限期，超时则强制 kill
+const GRACEFUL_SHUTDOWN_GRACE: Duration = Duration::from_secs(5);
+
+/// 每个服务在内存里保留的最近日志行数，崩溃后仍能看到最近发生了什么
+/// （比如 Sentry 上报时附带这份现场日志）
+const RECENT_LOG_LINES_CAP: usize = 200;
+
+/// 自动重启退避时长的上限，避免 `backoff_base_ms * 2^N` 无限增长
+const MAX_RESTART_BACKOFF: Duration = Duration::from_secs(30);
+
+/// 进程稳定运行超过这个时长再退出的，视为新的故障周期，重置重启计数
+const STABLE_UPTIME_THRESHOLD: Duration = Duration::from_secs(10);
+
+/// 就绪探测：每隔这么久尝试连接一次服务端口
+const READINESS_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// 就绪探测总超时：进程已经启动但这么久都没监听端口，就认为启动异常
+const READINESS_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Node (`server.js`) 和 Python (`subtitle_process_backend.py`) 默认监听的地址，
+/// 用于就绪探测区分"进程存活"和"端口已经能连上"
+const NODE_READINESS: (&str, u16) = ("127.0.0.1", 3000);
+const PYTHON_READINESS: (&str, u16) = ("127.0.0.1", 8000);
+
+/// 单个网络检查目标最多尝试几次（失败后短暂退避再重试）
+const NETWORK_CHECK_MAX_ATTEMPTS: u32 = 3;
+/// 网络检查重试之间的退避时长
+const NETWORK_CHECK_RETRY_BACKOFF: Duration = Duration::from_millis(300);
+
+/// 看门狗两次健康检查之间的间隔
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// 单次健康检查 HTTP 请求的超时
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// 连续多少次健康检查失败后判定服务已经卡死，需要强制重启
+const HEALTH_CHECK_FAILURE_THRESHOLD: u32 = 3;
+
+/// 单个落盘日志文件的大小上限，超过就滚动
+const LOG_FILE_MAX_SIZE_BYTES: u64 = 5 * 1024 * 1024;
+
+fn logs_dir() -> PathBuf {
+    launcher_data_dir().join("logs")
+}
+
+/// 把某个服务的 stdout/stderr 行持续追加写入磁盘文件，超过 `LOG_FILE_MAX_SIZE_BYTES` 就滚动；
+/// 与 `recent_logs` 内存环形缓冲区相互独立，前者是崩溃后现场排查用的持久记录
+struct RotatingLogFile {
+    dir: PathBuf,
+    base_name: String,
+    file: fs::File,
+    current_size: u64,
+    /// 最多保留几个滚动备份（`<name>.log.1` 最新，`.log.<max>` 最旧），来自
+    /// `LauncherSettings.log_retention`，用于控制磁盘占用
+    max_backups: u32,
+}
+
+impl RotatingLogFile {
+    fn open(base_name: &str, max_backups: u32) -> std::io::Result<Self> {
+        let dir = logs_dir();
+        fs::create_dir_all(&dir)?;
+        let path = dir.join(format!("{}.log", base_name));
+        let file = fs::OpenOptions::new().create(true).append(true).open(&path)?;
+        let current_size = file.metadata()?.len();
+        Ok(Self {
+            dir,
+            base_name: base_name.to_string(),
+            file,
+            current_size,
+            max_backups,
+        })
+    }
+
+    fn active_path(&self) -> PathBuf {
+        self.dir.join(format!("{}.log", self.base_name))
+    }
+
+    fn write_line(&mut self, line: &str) {
+        let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f");
+        let bytes = format!("[{}] {}\n", timestamp, line);
+        if self.file.write_all(bytes.as_bytes()).is_ok() {
+            self.current_size += bytes.len() as u64;
+        }
+        if self.current_size >= LOG_FILE_MAX_SIZE_BYTES {
+            self.rotate();
+        }
+    }
+
+    fn rotate(&mut self) {
+        if self.max_backups == 0 {
+            if let Ok(f) = fs::OpenOptions::new()
+                .create(true)
+                .write(true)
+                .truncate(true)
+                .open(self.active_path())
+            {
+                self.file = f;
+                self.current_size = 0;
+            }
+            return;
+        }
+
+        // 超出保留数量的最旧备份直接删除，再把其余备份依次后移一位
+        let oldest = self
+            .dir
+            .join(format!("{}.log.{}", self.base_name, self.max_backups));
+        fs::remove_file(&oldest).ok();
+        for i in (1..self.max_backups).rev() {
+            let from = self.dir.join(format!("{}.log.{}", self.base_name, i));
+            let to = self.dir.join(format!("{}.log.{}", self.base_name, i + 1));
+            if from.exists() {
+                fs::rename(&from, &to).ok();
+            }
+        }
+
+        let active_path = self.active_path();
+        let backup1 = self.dir.join(format!("{}.log.1", self.base_name));
+        fs::rename(&active_path, &backup1).ok();
+
+        if let Ok(f) = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&active_path)
+        {
+            self.file = f;
+            self.current_size = 0;
+        }
+    }
+}
+
+/// 经子进程 stdin 按行发送的 JSON-RPC 请求（换行分隔的 JSON，即 NDJSON）。
+/// `id` 由调用方单调递增分配，用于在 stdout 中匹配对应的 `RpcResponse`
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct RpcRequest {
+    id: u64,
+    method: String,
+    params: serde_json::Value,
+}
+
+/// 后端从 stdout 回传的 JSON-RPC 响应，按 `id` 与发出的 `RpcRequest` 关联
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct RpcResponse {
+    id: u64,
+    #[serde(default)]
+    result: Option<serde_json::Value>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
 // ─────────────────────────── App State ───────────────────────────────────────
 
-#[derive(Default)]
 struct ProcessState {
     child: Option<Child>,
     status: String,
+    recent_logs: VecDeque<String>,
+    /// 连续自动重启的次数，进程稳定运行过 `STABLE_UPTIME_THRESHOLD` 后清零
+    restart_attempts: u32,
+    started_at: Option<Instant>,
+    /// 是否启用看门狗（健康检查 + 崩溃后自动重启）；每个服务独立开关，调试时可以临时关掉
+    watchdog_enabled: bool,
+    /// 落盘的滚动日志文件句柄；懒加载，第一次写日志时才打开
+    log_file: Option<RotatingLogFile>,
+    /// 用户是否主动请求过停止；由 `stop_process` 置位，`spawn_process`/`spawn_once`
+    /// 重新拉起进程时清零。退避等待期间会重新检查这个标志，
+    /// 避免把用户在等待重启时点的 Stop 悄悄吞掉
+    shutdown_requested: bool,
+    /// 子进程的 stdin 句柄，供 `send_backend_command` 写入 NDJSON 格式的 JSON-RPC 请求；
+    /// 仅在进程运行期间存在
+    stdin: Option<Arc<tokio::sync::Mutex<tokio::process::ChildStdin>>>,
+    /// 按请求 id 登记等待响应的发送端；stdout 读取任务每解析出一条合法的 `RpcResponse`
+    /// 就据此投递给对应的调用方，而不是当作普通日志行转发
+    pending_rpc: Arc<Mutex<HashMap<u64, tokio::sync::oneshot::Sender<RpcResponse>>>>,
+    /// 下一个待分配的 JSON-RPC 请求 id，单调递增，跨重启保留
+    next_rpc_id: u64,
+}
+
+impl Default for ProcessState {
+    fn default() -> Self {
+        Self {
+            child: None,
+            status: String::new(),
+            recent_logs: VecDeque::new(),
+            restart_attempts: 0,
+            started_at: None,
+            watchdog_enabled: true,
+            log_file: None,
+            shutdown_requested: false,
+            stdin: None,
+            pending_rpc: Arc::new(Mutex::new(HashMap::new())),
+            next_rpc_id: 1,
+        }
+    }
+}
+
+/// 隧道客户端子进程的运行时状态；结构上比 `ProcessState` 简单得多——没有自动重启、
+/// 健康探测或 JSON-RPC，只关心"在不在跑"以及解析出来的公网地址
+#[derive(Default)]
+struct TunnelState {
+    child: Option<Child>,
+    status: String,
+    /// 从客户端 stdout 解析出的公网访问地址，解析成功前为 None
+    public_url: Option<String>,
 }
 
 struct AppState {
     node_server: Arc<Mutex<ProcessState>>,
     python_server: Arc<Mutex<ProcessState>>,
+    tunnel: Arc<Mutex<TunnelState>>,
 }
 
 impl AppState {
@@ -252,6 +657,7 @@ impl AppState {
         Self {
             node_server: Arc::new(Mutex::new(ProcessState::default())),
             python_server: Arc::new(Mutex::new(ProcessState::default())),
+            tunnel: Arc::new(Mutex::new(TunnelState::default())),
         }
     }
 }
@@ -279,8 +685,50 @@ struct EnvCheckUpdate {
 #[derive(Clone, Serialize)]
 struct NetworkCheckUpdate {
     url: String,
+    /// 检查目标所属分组（如 "Upstream Sites"、"Local Services"），用于在诊断面板里分区展示
+    group: String,
     status: String,
+    /// 成功尝试中的最小延迟（毫秒），供兼容旧展示逻辑使用
+    latency_ms: Option<u128>,
+    /// 已尝试的次数（含失败的重试），最多为 `NETWORK_CHECK_MAX_ATTEMPTS`
+    attempts: u32,
+    /// 成功尝试的延迟中位数（毫秒）；全部尝试都失败时为 None
+    median_latency_ms: Option<u128>,
+    via_proxy: bool,
+}
+
+#[derive(Clone, Serialize)]
+struct ProxyTestResult {
+    reachable: bool,
     latency_ms: Option<u128>,
+    message: String,
+}
+
+#[derive(Clone, Serialize)]
+struct BackendLogEvent {
+    source: &'static str,
+    stream: &'static str,
+    line: String,
+}
+
+#[derive(Clone, Serialize)]
+struct BackendStatusEvent {
+    process: String,
+    state: &'static str,
+}
+
+/// 在 starting/running/crashed/restarting/gave-up/stopped 之间的每次状态转换上
+/// 都发一条 `backend://status`，供前端展示健康状态；与 `server-status-changed`
+/// 并存——后者只是"重新拉一次 get_server_status"的通知，不带状态名
+fn emit_backend_status(app: &AppHandle, process_name: &str, state: &'static str) {
+    app.emit(
+        "backend://status",
+        BackendStatusEvent {
+            process: process_name.to_string(),
+            state,
+        },
+    )
+    .ok();
 }
 
 // ──────────────────────── Process Management ─────────────────────────────────
@@ -292,6 +740,7 @@ fn is_process_running(state: &Arc<Mutex<ProcessState>>) -> bool {
             Ok(Some(status)) => {
                 st.status = format!("Exited: {}", status);
                 st.child = None;
+                st.stdin = None;
                 false
             }
             Ok(None) => true,
@@ -302,81 +751,509 @@ fn is_process_running(state: &Arc<Mutex<ProcessState>>) -> bool {
     }
 }
 
-fn stop_process(state: Arc<Mutex<ProcessState>>, name: &str, app: &AppHandle) {
-    let mut st = state.lock().unwrap();
-    if let Some(mut child) = st.child.take() {
-        let pid = child.id();
-        let mut stopped_normally = false;
-        match child.kill() {
-            Ok(_) => {
-                let _ = child.wait();
-                stopped_normally = true;
-            }
-            Err(e) => {
-                app.emit(
-                    "log-message",
-                    LogMessage {
-                        line: format!("[{}] child.kill() error: {}", name, e),
-                    },
-                )
-                .ok();
-            }
-        }
+/// Unix: 向子进程所在的整个进程组发送信号（负 PID 表示目标为进程组）。
+/// `spawn_process` 通过 `setsid()` 让子进程成为组长，因此它的 PID 即为 PGID，
+/// 这样 node/python 派生的 ffmpeg 等子进程不会在重启时变成孤儿
+#[cfg(unix)]
+fn signal_process_group(pgid: i32, signal: i32) {
+    unsafe {
+        libc::kill(-pgid, signal);
+    }
+}
+
+/// 后台弹出系统通知，让 `start_minimized` 时用户也能知道 Node/Python 挂了。
+/// 通知只负责提醒，不做点击聚焦：`notify-rust` 在大多数 Linux 通知守护进程上
+/// 并不支持可点击 action，聚焦窗口仍需用户自己切换回来
+fn notify_service_event(enabled: bool, summary: &str, body: &str) {
+    if !enabled {
+        return;
+    }
+    if let Err(e) = notify_rust::Notification::new()
+        .summary(summary)
+        .body(body)
+        .show()
+    {
+        eprintln!("Failed to show desktop notification: {}", e);
+    }
+}
+
+/// 子进程以非零状态退出时上报一条 Sentry 消息，附带退出码和 `recent_logs`
+/// 里最近的若干行输出，方便在没有现场访问权限的情况下排查崩溃原因
+const CRASH_CONTEXT_LOG_LINES: usize = 20;
+
+fn capture_crash(
+    process_name: &str,
+    exit_status: &std::process::ExitStatus,
+    process_state: &Arc<Mutex<ProcessState>>,
+) {
+    let tail: Vec<String> = {
+        let st = process_state.lock().unwrap();
+        st.recent_logs
+            .iter()
+            .rev()
+            .take(CRASH_CONTEXT_LOG_LINES)
+            .rev()
+            .cloned()
+            .collect()
+    };
+    sentry::with_scope(
+        |scope| {
+            scope.set_extra("exit_code", exit_status.code().into());
+            scope.set_extra("recent_logs", tail.join("\n").into());
+        },
+        || {
+            sentry::capture_message(
+                &format!("{} exited with {}", process_name, exit_status),
+                sentry::Level::Error,
+            );
+        },
+    );
+}
+
+/// Windows 上没有 SIGTERM 的等价物，先尝试不带 `/F` 的 `taskkill` 让进程
+/// 有机会自行清理，调用方在宽限期超时后再升级为 `/F /T` 强杀整棵进程树
+#[cfg(target_os = "windows")]
+fn request_polite_close(pid: u32) {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x08000000;
+    let _ = std::process::Command::new("taskkill")
+        .args(["/PID", &pid.to_string()])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output();
+}
+
+async fn stop_process(state: Arc<Mutex<ProcessState>>, name: &str, app: &AppHandle) {
+    let child = {
+        let mut st = state.lock().unwrap();
+        st.shutdown_requested = true;
+        st.stdin = None;
+        st.child.take()
+    };
+    let Some(mut child) = child else {
+        state.lock().unwrap().status = "Not running".to_string();
+        return;
+    };
+
+    if let Some(pid) = child.id() {
+        app.emit(
+            "log-message",
+            LogMessage {
+                line: format!("[{}] Requested graceful stop (PID {}).", name, pid),
+            },
+        )
+        .ok();
+        // Signalling/spawning a killer process is blocking; run it on the
+        // blocking pool so it doesn't stall the async runtime's workers.
+        tokio::task::spawn_blocking(move || {
+            #[cfg(unix)]
+            signal_process_group(pid as i32, libc::SIGTERM);
+            #[cfg(target_os = "windows")]
+            request_polite_close(pid);
+        })
+        .await
+        .ok();
+    }
 
-        if stopped_normally {
+    let status = match tokio::time::timeout(GRACEFUL_SHUTDOWN_GRACE, child.wait()).await {
+        Ok(Ok(status)) => {
             app.emit(
                 "log-message",
                 LogMessage {
-                    line: format!("[{}] Process stopped.", name),
+                    line: format!("[{}] Process stopped gracefully ({}).", name, status),
                 },
             )
             .ok();
-            st.status = "Stopped".to_string();
-        } else {
+            "Stopped".to_string()
+        }
+        _ => {
+            // 宽限期内没有退出，升级为强制终止整个进程组/进程树
+            app.emit(
+                "log-message",
+                LogMessage {
+                    line: format!(
+                        "[{}] Did not exit within {:?}, escalated to force kill.",
+                        name, GRACEFUL_SHUTDOWN_GRACE
+                    ),
+                },
+            )
+            .ok();
+
+            #[cfg(unix)]
+            {
+                if let Some(pid) = child.id() {
+                    let _ = tokio::task::spawn_blocking(move || {
+                        signal_process_group(pid as i32, libc::SIGKILL)
+                    })
+                    .await;
+                }
+                let _ = child.wait().await;
+                "Stopped (killed)".to_string()
+            }
+
             #[cfg(target_os = "windows")]
             {
-                let pid_str = pid.to_string();
-                match Command::new("taskkill")
-                    .args(["/PID", &pid_str, "/T", "/F"])
-                    .output()
-                {
-                    Ok(output) => {
-                        if output.status.success() {
+                // `child.kill()` only terminates the direct child, not the tree it may have
+                // spawned (e.g. ffmpeg/worker subprocesses) — escalate via `taskkill /T /F`
+                // the same way the health-watchdog force-restart path does.
+                if let Some(pid) = child.id() {
+                    let kill_result = tokio::task::spawn_blocking(move || {
+                        std::process::Command::new("taskkill")
+                            .args(["/PID", &pid.to_string(), "/T", "/F"])
+                            .output()
+                    })
+                    .await;
+                    let _ = child.wait().await;
+                    match kill_result {
+                        Ok(Ok(output)) if output.status.success() => "Stopped (killed)".to_string(),
+                        Ok(Ok(output)) => {
                             app.emit(
                                 "log-message",
                                 LogMessage {
-                                    line: format!("[{}] taskkill succeeded for PID {}.", name, pid),
+                                    line: format!(
+                                        "[{}] taskkill failed for PID {}. stderr: {}",
+                                        name,
+                                        pid,
+                                        String::from_utf8_lossy(&output.stderr)
+                                    ),
                                 },
                             )
                             .ok();
-                            st.status = "Stopped (taskkill)".to_string();
-                        } else {
-                            st.status = format!("Failed to stop: taskkill failed (PID {})", pid);
+                            "Failed to stop process".to_string()
+                        }
+                        _ => {
+                            app.emit(
+                                "log-message",
+                                LogMessage {
+                                    line: format!("[{}] Failed to run taskkill for PID {}.", name, pid),
+                                },
+                            )
+                            .ok();
+                            "Failed to stop process".to_string()
                         }
                     }
-                    Err(e) => {
-                        st.status = format!("Failed to stop: {}", e);
-                    }
+                } else {
+                    let _ = child.kill().await;
+                    let _ = child.wait().await;
+                    "Stopped (killed)".to_string()
                 }
             }
-            #[cfg(not(target_os = "windows"))]
+        }
+    };
+
+    state.lock().unwrap().status = status;
+    emit_backend_status(app, name, "stopped");
+    app.emit("server-status-changed", serde_json::json!({}))
+        .ok();
+}
+
+/// JSON-RPC 请求的默认超时：查询进度/取消任务这类交互应该很快有响应，
+/// 卡住多半是后端已经失联，没必要无限等
+const RPC_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 向已建立 stdin 管道的后端子进程发送一条 NDJSON 格式的 JSON-RPC 请求，并等待
+/// 同一个 `id` 对应的响应（或超时）。用于查询进度、取消任务等结构化交互场景，
+/// 而不必靠抓取/解析日志文本
+async fn dispatch_rpc_command(
+    process_state: &Arc<Mutex<ProcessState>>,
+    method: &str,
+    params: serde_json::Value,
+) -> Result<serde_json::Value, String> {
+    let (stdin, pending_rpc, id) = {
+        let mut state = process_state.lock().unwrap();
+        let stdin = state
+            .stdin
+            .clone()
+            .ok_or_else(|| "Process is not running".to_string())?;
+        let id = state.next_rpc_id;
+        state.next_rpc_id += 1;
+        (stdin, Arc::clone(&state.pending_rpc), id)
+    };
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    pending_rpc.lock().unwrap().insert(id, tx);
+
+    let request = RpcRequest {
+        id,
+        method: method.to_string(),
+        params,
+    };
+    let line = serde_json::to_string(&request)
+        .map_err(|e| format!("Failed to serialize RPC request: {}", e))?;
+
+    {
+        let mut writer = stdin.lock().await;
+        if let Err(e) = writer.write_all(format!("{}\n", line).as_bytes()).await {
+            pending_rpc.lock().unwrap().remove(&id);
+            return Err(format!("Failed to write to stdin: {}", e));
+        }
+    }
+
+    match tokio::time::timeout(RPC_TIMEOUT, rx).await {
+        Ok(Ok(response)) => match response.error {
+            Some(err) => Err(err),
+            None => Ok(response.result.unwrap_or(serde_json::Value::Null)),
+        },
+        Ok(Err(_)) => {
+            pending_rpc.lock().unwrap().remove(&id);
+            Err("Backend disconnected before responding".to_string())
+        }
+        Err(_) => {
+            pending_rpc.lock().unwrap().remove(&id);
+            Err("Timed out waiting for backend response".to_string())
+        }
+    }
+}
+
+/// 供前端按结构化 RPC 方式查询进度、取消任务、或查看已加载模型状态，
+/// 而不必抓取/解析日志文本；`name` 选择目标后端（"node" 或 "python"）
+#[tauri::command]
+async fn send_backend_command(
+    name: String,
+    method: String,
+    params: serde_json::Value,
+    state: State<'_, AppState>,
+) -> Result<serde_json::Value, String> {
+    let process_state = match name.to_lowercase().as_str() {
+        "node" => Arc::clone(&state.node_server),
+        "python" => Arc::clone(&state.python_server),
+        other => return Err(format!("Unknown backend: {}", other)),
+    };
+    dispatch_rpc_command(&process_state, &method, params).await
+}
+
+/// 列出 `logs_dir()` 下属于某个服务的历史落盘日志文件（当前会话 + 各级滚动备份），
+/// 按 `<name>.log` 在前、`<name>.log.1`/`.2`/... 依次在后排序
+#[tauri::command]
+fn get_log_files(process_name: String) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(logs_dir()) else {
+        return Vec::new();
+    };
+    let prefix = format!("{}.log", process_name.to_lowercase());
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .map(|n| n.starts_with(&prefix))
+                .unwrap_or(false)
+        })
+        .collect();
+    files.sort_by_key(|path| {
+        path.file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|n| n.strip_prefix(&format!("{}.", prefix)))
+            .and_then(|suffix| suffix.parse::<u32>().ok())
+            .unwrap_or(0)
+    });
+    files
+}
+
+/// 读取某个落盘日志文件的最后 `lines` 行，供前端的日志浏览面板按需加载历史内容
+#[tauri::command]
+fn read_log_tail(path: PathBuf, lines: usize) -> Result<String, String> {
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let tail: Vec<&str> = content.lines().rev().take(lines).collect();
+    Ok(tail.into_iter().rev().collect::<Vec<_>>().join("\n"))
+}
+
+/// 从隧道客户端的一行 stdout 中提取分配到的公网地址（http/https/tcp），用于回传给前端
+fn parse_tunnel_url(line: &str) -> Option<String> {
+    use once_cell::sync::Lazy;
+    static TUNNEL_URL_RE: Lazy<regex::Regex> = Lazy::new(|| {
+        regex::Regex::new(r"\b(?:https?|tcp)://[A-Za-z0-9.\-]+(?::\d+)?(?:/\S*)?").unwrap()
+    });
+    TUNNEL_URL_RE.find(line).map(|m| m.as_str().to_string())
+}
+
+/// 启动打包的隧道客户端，把本地 Node 服务暴露到 `tunnel_server_addr`；客户端 stdout
+/// 里第一条能解析出 URL 的行即视为分配好的公网地址，随 `tunnel://public-url` 事件
+/// 回传给前端，同时刷新托盘提示
+#[tauri::command]
+async fn start_tunnel(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let tunnel_state = Arc::clone(&state.tunnel);
+    {
+        let mut st = tunnel_state.lock().unwrap();
+        if st.child.is_some() {
+            return Err("Tunnel already running".to_string());
+        }
+        st.status = "Starting".to_string();
+        st.public_url = None;
+    }
+
+    let settings = load_launcher_settings();
+    if settings.tunnel_client_path.trim().is_empty() || settings.tunnel_server_addr.trim().is_empty()
+    {
+        let mut st = tunnel_state.lock().unwrap();
+        st.status = "Not configured".to_string();
+        return Err("tunnel_client_path/tunnel_server_addr not configured".to_string());
+    }
+
+    let local_port = NODE_READINESS.1;
+    let mut cmd = Command::new(&settings.tunnel_client_path);
+    cmd.args([
+        "-server_addr",
+        &settings.tunnel_server_addr,
+        "-token",
+        &settings.tunnel_token,
+        "-local_port",
+        &local_port.to_string(),
+        "-remote_port",
+        &settings.tunnel_remote_port.to_string(),
+    ]);
+    if !settings.tunnel_subdomain.trim().is_empty() {
+        cmd.args(["-subdomain", &settings.tunnel_subdomain]);
+    }
+    cmd.stdout(Stdio::piped());
+    cmd.stderr(Stdio::piped());
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    match cmd.spawn() {
+        Ok(mut child) => {
+            let stdout = child.stdout.take().expect("Failed to open tunnel stdout");
+            let stderr = child.stderr.take().expect("Failed to open tunnel stderr");
+
             {
-                st.status = "Failed to stop process".to_string();
+                let mut st = tunnel_state.lock().unwrap();
+                st.child = Some(child);
+                st.status = "Running".to_string();
             }
+            update_tray_menu(&app);
+
+            let out_state = Arc::clone(&tunnel_state);
+            let out_app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let mut reader = BufReader::new(stdout).lines();
+                while let Ok(Some(line)) = reader.next_line().await {
+                    if let Some(url) = parse_tunnel_url(&line) {
+                        let is_new = {
+                            let mut st = out_state.lock().unwrap();
+                            if st.public_url.is_none() {
+                                st.public_url = Some(url.clone());
+                                true
+                            } else {
+                                false
+                            }
+                        };
+                        if is_new {
+                            out_app
+                                .emit("tunnel://public-url", serde_json::json!({ "url": url }))
+                                .ok();
+                            update_tray_menu(&out_app);
+                        }
+                    }
+                    out_app
+                        .emit(
+                            "log-message",
+                            LogMessage {
+                                line: format!("[tunnel-stdout] {}", line),
+                            },
+                        )
+                        .ok();
+                }
+            });
+
+            let err_app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let mut reader = BufReader::new(stderr).lines();
+                while let Ok(Some(line)) = reader.next_line().await {
+                    err_app
+                        .emit(
+                            "log-message",
+                            LogMessage {
+                                line: format!("[tunnel-stderr] {}", line),
+                            },
+                        )
+                        .ok();
+                }
+            });
+
+            Ok(())
+        }
+        Err(e) => {
+            let mut st = tunnel_state.lock().unwrap();
+            st.status = format!("Failed to start: {}", e);
+            Err(format!("Failed to start tunnel client: {}", e))
         }
-    } else {
-        st.status = "Not running".to_string();
     }
 }
 
-fn spawn_process(
+/// 停止隧道客户端；应用退出时（`"quit"` 托盘菜单项）与 `stop_process` 一样无条件调用一次
+#[tauri::command]
+async fn stop_tunnel(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let tunnel_state = Arc::clone(&state.tunnel);
+    let child = {
+        let mut st = tunnel_state.lock().unwrap();
+        st.status = "Stopped".to_string();
+        st.public_url = None;
+        st.child.take()
+    };
+    if let Some(mut child) = child {
+        let _ = child.kill().await;
+    }
+    update_tray_menu(&app);
+    Ok(())
+}
+
+/// 把一行输出既发给前端（实时控制台）又存进内存环形缓冲区（崩溃后的现场日志）
+fn record_and_emit_log(
+    app: &AppHandle,
+    state: &Arc<Mutex<ProcessState>>,
+    process_name: &'static str,
+    stream: &'static str,
+    line: String,
+) {
+    {
+        let mut st = state.lock().unwrap();
+        st.recent_logs.push_back(format!("[{}] {}", stream, line));
+        if st.recent_logs.len() > RECENT_LOG_LINES_CAP {
+            st.recent_logs.pop_front();
+        }
+
+        if st.log_file.is_none() {
+            let max_backups = load_launcher_settings().log_retention;
+            st.log_file = RotatingLogFile::open(&process_name.to_lowercase(), max_backups).ok();
+        }
+        if let Some(log_file) = st.log_file.as_mut() {
+            log_file.write_line(&format!("[{}] {}", stream, line));
+        }
+    }
+    app.emit(
+        "backend://log",
+        BackendLogEvent {
+            source: process_name,
+            stream,
+            line: line.clone(),
+        },
+    )
+    .ok();
+    app.emit(
+        "log-line",
+        BackendLogEvent {
+            source: process_name,
+            stream,
+            line,
+        },
+    )
+    .ok();
+}
+
+/// 拉起一次子进程并接管它的 stdout/stderr；不负责重启策略，只管这一次生命周期
+async fn spawn_once(
     command: &str,
     args: &[&str],
     working_dir: Option<&str>,
-    app: AppHandle,
-    process_state: Arc<Mutex<ProcessState>>,
+    readiness: Option<(&str, u16)>,
+    app: &AppHandle,
+    process_state: &Arc<Mutex<ProcessState>>,
     process_name: &'static str,
-) {
+) -> bool {
     let mut cmd = Command::new(command);
     cmd.args(args);
 
@@ -395,6 +1272,7 @@ fn spawn_process(
         }
     }
 
+    cmd.stdin(Stdio::piped());
     cmd.stdout(Stdio::piped());
     cmd.stderr(Stdio::piped());
 
@@ -405,124 +1283,633 @@ fn spawn_process(
         cmd.creation_flags(CREATE_NO_WINDOW);
     }
 
-    let mut state = process_state.lock().unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        unsafe {
+            cmd.pre_exec(|| {
+                // 让子进程成为新会话/进程组的组长，这样 stop_process 才能用
+                // 负 PID 把整个组（包括 node/python 派生的 ffmpeg 等）一并信号掉
+                libc::setsid();
+                Ok(())
+            });
+        }
+    }
+
+    emit_backend_status(app, process_name, "starting");
+
     match cmd.spawn() {
         Ok(mut child) => {
             app.emit(
                 "log-message",
                 LogMessage {
-                    line: format!("[{}] Process started (PID: {}).", process_name, child.id()),
+                    line: format!(
+                        "[{}] Process started (PID: {}).",
+                        process_name,
+                        child.id().unwrap_or(0)
+                    ),
                 },
             )
             .ok();
+            sentry::add_breadcrumb(sentry::Breadcrumb {
+                category: Some("process".to_string()),
+                message: Some(format!(
+                    "{} spawned (PID {})",
+                    process_name,
+                    child.id().unwrap_or(0)
+                )),
+                level: sentry::Level::Info,
+                ..Default::default()
+            });
 
+            let stdin = child.stdin.take().expect("Failed to open stdin");
             let stdout = child.stdout.take().expect("Failed to open stdout");
             let stderr = child.stderr.take().expect("Failed to open stderr");
 
-            state.child = Some(child);
-            state.status = "Running".to_string();
-            drop(state); // release lock before emitting
+            {
+                let mut state = process_state.lock().unwrap();
+                state.child = Some(child);
+                state.status = "Running".to_string();
+                state.started_at = Some(Instant::now());
+                state.shutdown_requested = false;
+                state.stdin = Some(Arc::new(tokio::sync::Mutex::new(stdin)));
+            }
 
-            // Emit status changed event
+            emit_backend_status(app, process_name, "running");
             app.emit("server-status-changed", serde_json::json!({}))
                 .ok();
 
-            // stdout reader
-            let app_out = app.clone();
-            thread::spawn(move || {
-                let reader = BufReader::new(stdout);
-                for line in reader.lines() {
-                    if let Ok(line) = line {
-                        app_out
-                            .emit(
-                                "log-message",
-                                LogMessage {
-                                    line: format!("[{}-stdout] {}", process_name, line),
-                                },
-                            )
-                            .ok();
+            if let Some((host, port)) = readiness {
+                spawn_readiness_probe(
+                    app.clone(),
+                    Arc::clone(process_state),
+                    process_name,
+                    host.to_string(),
+                    port,
+                );
+            }
+
+            // Read both streams concurrently on a single task instead of a
+            // blocking thread per stream.
+            let app_io = app.clone();
+            let state_io = Arc::clone(process_state);
+            let pending_rpc = Arc::clone(&process_state.lock().unwrap().pending_rpc);
+            tauri::async_runtime::spawn(async move {
+                let mut stdout_lines = BufReader::new(stdout).lines();
+                let mut stderr_lines = BufReader::new(stderr).lines();
+                let mut stdout_done = false;
+                let mut stderr_done = false;
+
+                while !(stdout_done && stderr_done) {
+                    tokio::select! {
+                        line = stdout_lines.next_line(), if !stdout_done => {
+                            match line {
+                                Ok(Some(line)) => {
+                                    // 后端可能在 stdout 上混写普通日志和 NDJSON 格式的 JSON-RPC 回复；
+                                    // 先尝试按 RpcResponse 解析，命中已登记的 id 就投递给等待方，
+                                    // 否则按老规矩当作普通日志行转发
+                                    if let Ok(response) = serde_json::from_str::<RpcResponse>(&line) {
+                                        let waiting = pending_rpc.lock().unwrap().remove(&response.id);
+                                        if let Some(sender) = waiting {
+                                            sender.send(response).ok();
+                                            continue;
+                                        }
+                                    }
+                                    record_and_emit_log(&app_io, &state_io, process_name, "stdout", line);
+                                }
+                                _ => stdout_done = true,
+                            }
+                        }
+                        line = stderr_lines.next_line(), if !stderr_done => {
+                            match line {
+                                Ok(Some(line)) => {
+                                    record_and_emit_log(&app_io, &state_io, process_name, "stderr", line);
+                                }
+                                _ => stderr_done = true,
+                            }
+                        }
                     }
                 }
             });
 
-            // stderr reader
-            let app_err = app.clone();
-            thread::spawn(move || {
-                let reader = BufReader::new(stderr);
-                for line in reader.lines() {
-                    if let Ok(line) = line {
-                        app_err
-                            .emit(
-                                "log-message",
-                                LogMessage {
-                                    line: format!("[{}-stderr] {}", process_name, line),
-                                },
-                            )
-                            .ok();
-                    }
+            true
+        }
+        Err(e) => {
+            let msg = format!("[{}] Failed to start process: {}", process_name, e);
+            app.emit("log-message", LogMessage { line: msg.clone() })
+                .ok();
+            process_state.lock().unwrap().status = msg;
+            false
+        }
+    }
+}
+
+/// 轮询 `host:port` 直到能建立 TCP 连接或超时，用来区分"进程已拉起"和
+/// "服务已经在监听端口、真的能处理请求"
+async fn wait_for_ready(host: &str, port: u16, timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    loop {
+        let attempt = tokio::time::timeout(
+            Duration::from_millis(500),
+            tokio::net::TcpStream::connect((host, port)),
+        )
+        .await;
+        if matches!(attempt, Ok(Ok(_))) {
+            return true;
+        }
+        if Instant::now() >= deadline {
+            return false;
+        }
+        tokio::time::sleep(READINESS_POLL_INTERVAL).await;
+    }
+}
+
+/// 在后台探测就绪状态，成功/超时后更新 `status` 并发出 `server-ready`；
+/// 如果这期间进程已经退出（`status` 不再是 "Running"），探测结果就不作数了
+fn spawn_readiness_probe(
+    app: AppHandle,
+    process_state: Arc<Mutex<ProcessState>>,
+    process_name: &'static str,
+    host: String,
+    port: u16,
+) {
+    tauri::async_runtime::spawn(async move {
+        let ready = wait_for_ready(&host, port, READINESS_TIMEOUT).await;
+        let mut st = process_state.lock().unwrap();
+        if st.status != "Running" {
+            // 探测期间进程已经退出或被重启逻辑接管，这份结果已经过时
+            return;
+        }
+        st.status = if ready {
+            "Ready".to_string()
+        } else {
+            "Started but not responding".to_string()
+        };
+        drop(st);
+        if ready {
+            let settings = load_launcher_settings();
+            notify_service_event(
+                settings.notify_on_events,
+                &format!("{} ready", process_name),
+                "Accepting connections.",
+            );
+            spawn_health_watchdog(
+                app.clone(),
+                Arc::clone(&process_state),
+                process_name,
+                host.clone(),
+                port,
+            );
+        }
+        app.emit(
+            "server-ready",
+            serde_json::json!({ "process": process_name, "ready": ready }),
+        )
+        .ok();
+        app.emit("server-status-changed", serde_json::json!({ "process": process_name }))
+            .ok();
+    });
+}
+
+/// 看门狗：进程就绪之后持续对 `http://host:port/health` 做健康检查，连续
+/// `HEALTH_CHECK_FAILURE_THRESHOLD` 次失败就判定服务已经卡死（PID 活着但不
+/// 响应请求），强制杀掉整个进程组。**不**把 `child` 从 `process_state` 里摘走——
+/// 这样 `spawn_process` 里的监督循环能照常用 `try_wait` 发现这次退出，走一遍
+/// 正常的崩溃重启流程（指数退避、计数、`Crashed`/`Restarting` 状态）
+fn spawn_health_watchdog(
+    app: AppHandle,
+    process_state: Arc<Mutex<ProcessState>>,
+    process_name: &'static str,
+    host: String,
+    port: u16,
+) {
+    let health_url = format!("http://{}:{}/health", host, port);
+    tauri::async_runtime::spawn(async move {
+        let mut consecutive_failures = 0u32;
+        loop {
+            tokio::time::sleep(HEALTH_CHECK_INTERVAL).await;
+
+            let pid = {
+                let st = process_state.lock().unwrap();
+                if st.status != "Running" && st.status != "Ready" {
+                    // 进程已经不在跑了（停止/崩溃/已被重启逻辑接管），没必要继续探测
+                    break;
                 }
-            });
+                if !st.watchdog_enabled {
+                    consecutive_failures = 0;
+                    continue;
+                }
+                st.child.as_ref().and_then(|c| c.id())
+            };
+            let Some(pid) = pid else { break };
+
+            let url = health_url.clone();
+            let healthy = tokio::task::spawn_blocking(move || {
+                ureq::AgentBuilder::new()
+                    .timeout(HEALTH_CHECK_TIMEOUT)
+                    .build()
+                    .get(&url)
+                    .call()
+                    .is_ok()
+            })
+            .await
+            .unwrap_or(false);
+
+            if healthy {
+                consecutive_failures = 0;
+                continue;
+            }
+
+            consecutive_failures += 1;
+            app.emit(
+                "log-message",
+                LogMessage {
+                    line: format!(
+                        "[{}] Health check failed ({}/{}).",
+                        process_name, consecutive_failures, HEALTH_CHECK_FAILURE_THRESHOLD
+                    ),
+                },
+            )
+            .ok();
+
+            if consecutive_failures >= HEALTH_CHECK_FAILURE_THRESHOLD {
+                app.emit(
+                    "log-message",
+                    LogMessage {
+                        line: format!(
+                            "[{}] Unresponsive after {} failed health checks, force-restarting.",
+                            process_name, consecutive_failures
+                        ),
+                    },
+                )
+                .ok();
+                #[cfg(unix)]
+                signal_process_group(pid as i32, libc::SIGKILL);
+                #[cfg(target_os = "windows")]
+                {
+                    let _ = std::process::Command::new("taskkill")
+                        .args(["/PID", &pid.to_string(), "/T", "/F"])
+                        .output();
+                }
+                break;
+            }
+        }
+    });
+}
+
+/// 轮询直到子进程退出（或被 `stop_process` 摘走），返回退出状态；
+/// 返回 `None` 表示进程是被主动停止的，调用方不应该触发自动重启
+async fn wait_for_exit(process_state: &Arc<Mutex<ProcessState>>) -> Option<std::process::ExitStatus> {
+    loop {
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        let mut st = process_state.lock().unwrap();
+        let Some(child_ref) = st.child.as_mut() else {
+            return None;
+        };
+        match child_ref.try_wait() {
+            Ok(Some(exit_status)) => return Some(exit_status),
+            Ok(None) => {}
+            Err(_) => return None,
+        }
+    }
+}
+
+/// 拉起子进程并持续监督它：异常退出时按 `LauncherSettings` 里的重启策略自动
+/// 重启（指数退避，稳定运行一段时间后清零计数），直到用户主动停止或重启次数
+/// 耗尽。调用方在 `tauri::async_runtime::spawn` 里 fire-and-forget 地跑它。
+async fn spawn_process(
+    command: &str,
+    args: &[&str],
+    working_dir: Option<&str>,
+    readiness: Option<(&str, u16)>,
+    app: AppHandle,
+    process_state: Arc<Mutex<ProcessState>>,
+    process_name: &'static str,
+) {
+    // 手动 Start 可能和一次正在进行的自动重启撞上：已经有活着的子进程就不再
+    // 重复拉起第二个，避免把前一个晾成孤儿
+    {
+        let mut st = process_state.lock().unwrap();
+        if let Some(existing) = st.child.as_mut() {
+            if matches!(existing.try_wait(), Ok(None)) {
+                app.emit(
+                    "log-message",
+                    LogMessage {
+                        line: format!("[{}] Already running, start skipped.", process_name),
+                    },
+                )
+                .ok();
+                return;
+            }
+            st.child = None;
+            st.stdin = None;
+        }
+    }
+
+    if !spawn_once(
+        command,
+        args,
+        working_dir,
+        readiness,
+        &app,
+        &process_state,
+        process_name,
+    )
+    .await
+    {
+        return;
+    }
+
+    loop {
+        let Some(exit_status) = wait_for_exit(&process_state).await else {
+            // `child` 已被 stop_process 取走，是用户主动停止，不需要重启
+            break;
+        };
+
+        let ran_long_enough = process_state
+            .lock()
+            .unwrap()
+            .started_at
+            .map(|t| t.elapsed() >= STABLE_UPTIME_THRESHOLD)
+            .unwrap_or(false);
+        if ran_long_enough {
+            process_state.lock().unwrap().restart_attempts = 0;
+        }
+
+        {
+            let mut st = process_state.lock().unwrap();
+            st.status = format!("Exited: {}", exit_status);
+            st.child = None;
+            st.started_at = None;
+            st.stdin = None;
+        }
+        app.emit(
+            "log-message",
+            LogMessage {
+                line: format!("[{}] Process exited: {}", process_name, exit_status),
+            },
+        )
+        .ok();
+        app.emit(
+            "server-status-changed",
+            serde_json::json!({ "process": process_name }),
+        )
+        .ok();
+
+        let settings = load_launcher_settings();
+        if exit_status.success() {
+            break;
+        }
+
+        emit_backend_status(&app, process_name, "crashed");
+        capture_crash(process_name, &exit_status, &process_state);
+        notify_service_event(
+            settings.notify_on_events,
+            &format!("{} crashed", process_name),
+            &format!("Exited with {}", exit_status),
+        );
+
+        if !settings.restart_on_crash || !process_state.lock().unwrap().watchdog_enabled {
+            break;
+        }
+
+        let attempt = {
+            let mut st = process_state.lock().unwrap();
+            st.restart_attempts += 1;
+            st.restart_attempts
+        };
+        if attempt > settings.max_restarts {
+            let mut st = process_state.lock().unwrap();
+            st.status = format!("Crashed (gave up after {} restarts)", attempt - 1);
+            drop(st);
+            app.emit(
+                "log-message",
+                LogMessage {
+                    line: format!(
+                        "[{}] Giving up after {} restart attempts.",
+                        process_name,
+                        attempt - 1
+                    ),
+                },
+            )
+            .ok();
+            emit_backend_status(&app, process_name, "gave-up");
+            app.emit("server-status-changed", serde_json::json!({ "process": process_name }))
+                .ok();
+            notify_service_event(
+                settings.notify_on_events,
+                &format!("{} restart budget exceeded", process_name),
+                &format!("Gave up after {} restart attempts", attempt - 1),
+            );
+            break;
+        }
+
+        let backoff = Duration::from_millis(
+            settings
+                .backoff_base_ms
+                .saturating_mul(1u64 << (attempt - 1).min(16)),
+        )
+        .min(MAX_RESTART_BACKOFF);
+        process_state.lock().unwrap().status =
+            format!("Restarting (attempt {} in {:?})", attempt, backoff);
+        emit_backend_status(&app, process_name, "restarting");
+        app.emit(
+            "log-message",
+            LogMessage {
+                line: format!(
+                    "[{}] Auto-restarting in {:?} (attempt {}).",
+                    process_name, backoff, attempt
+                ),
+            },
+        )
+        .ok();
+        tokio::time::sleep(backoff).await;
+
+        // 退避等待期间用户可能已经点了 Stop；此时没有活着的 child 可取，
+        // stop_process 只会置位 shutdown_requested，这里要重新检查一遍，
+        // 否则会把用户主动停止的请求悄悄吞掉，又把进程拉起来
+        if process_state.lock().unwrap().shutdown_requested {
+            app.emit(
+                "log-message",
+                LogMessage {
+                    line: format!(
+                        "[{}] Stop requested during backoff, aborting restart.",
+                        process_name
+                    ),
+                },
+            )
+            .ok();
+            break;
+        }
+
+        if !spawn_once(
+            command,
+            args,
+            working_dir,
+            readiness,
+            &app,
+            &process_state,
+            process_name,
+        )
+        .await
+        {
+            break;
+        }
+        app.emit(
+            "server-restarted",
+            serde_json::json!({ "process": process_name, "attempt": attempt }),
+        )
+        .ok();
+    }
+}
+
+/// 被监听文件发生变化时应该重启的后端
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum WatchTarget {
+    Node,
+    Python,
+    /// config.json 两个后端都会读取，变化后两个都要重启
+    Config,
+}
+
+/// 文件改动的防抖时长：编辑器保存往往触发好几次 modify 事件
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// 监听 `./src/config.json`、`./src/server.js`、`./src/subtitle_process_backend.py`，
+/// 变化经过 `WATCH_DEBOUNCE` 防抖后自动重启受影响的后端，调整 prompt/模型配置或
+/// 改后端代码时不用手动停止再启动
+fn spawn_backend_watcher(app: AppHandle) {
+    use notify::{RecursiveMode, Watcher};
+
+    let watch_paths: Vec<(PathBuf, WatchTarget)> = vec![
+        (PathBuf::from("./src/config.json"), WatchTarget::Config),
+        (PathBuf::from("./src/server.js"), WatchTarget::Node),
+        (
+            PathBuf::from("./src/subtitle_process_backend.py"),
+            WatchTarget::Python,
+        ),
+    ];
+
+    let (raw_tx, raw_rx) = std::sync::mpsc::channel();
+    let mut watcher = match notify::recommended_watcher(raw_tx) {
+        Ok(w) => w,
+        Err(e) => {
+            app.emit(
+                "log-message",
+                LogMessage {
+                    line: format!("[watch] Failed to create file watcher: {}", e),
+                },
+            )
+            .ok();
+            return;
+        }
+    };
+    for (path, _) in &watch_paths {
+        if let Err(e) = watcher.watch(path, RecursiveMode::NonRecursive) {
+            app.emit(
+                "log-message",
+                LogMessage {
+                    line: format!("[watch] Failed to watch {}: {}", path.display(), e),
+                },
+            )
+            .ok();
+        }
+    }
 
-            // monitor thread
-            let monitor_state = Arc::clone(&process_state);
-            let app_monitor = app.clone();
-            thread::spawn(move || loop {
-                thread::sleep(std::time::Duration::from_millis(300));
-                let mut st = monitor_state.lock().unwrap();
-                if let Some(child_ref) = st.child.as_mut() {
-                    match child_ref.try_wait() {
-                        Ok(Some(exit_status)) => {
-                            app_monitor
-                                .emit(
-                                    "log-message",
-                                    LogMessage {
-                                        line: format!(
-                                            "[{}] Process exited: {}",
-                                            process_name, exit_status
-                                        ),
-                                    },
-                                )
-                                .ok();
-                            st.status = format!("Exited: {}", exit_status);
-                            st.child = None;
-                            drop(st);
-                            // emit status update
-                            app_monitor
-                                .emit(
-                                    "server-status-changed",
-                                    serde_json::json!({ "process": process_name }),
-                                )
-                                .ok();
-                            break;
-                        }
-                        Ok(None) => {}
-                        Err(e) => {
-                            app_monitor
-                                .emit(
-                                    "log-message",
-                                    LogMessage {
-                                        line: format!("[{}] try_wait error: {}", process_name, e),
-                                    },
-                                )
-                                .ok();
+    thread::spawn(move || {
+        // 把 watcher 移进线程里存活，离开作用域被 drop 会立即停止监听
+        let _watcher = watcher;
+        let mut pending: std::collections::HashMap<WatchTarget, Instant> =
+            std::collections::HashMap::new();
+
+        loop {
+            match raw_rx.recv_timeout(Duration::from_millis(100)) {
+                Ok(Ok(event)) => {
+                    if matches!(
+                        event.kind,
+                        notify::EventKind::Modify(_) | notify::EventKind::Create(_)
+                    ) {
+                        for event_path in &event.paths {
+                            for (watched_path, target) in &watch_paths {
+                                if event_path == watched_path {
+                                    pending.insert(target.clone(), Instant::now());
+                                }
+                            }
                         }
                     }
-                } else {
-                    break;
                 }
-            });
-        }
-        Err(e) => {
-            let msg = format!("[{}] Failed to start process: {}", process_name, e);
-            app.emit("log-message", LogMessage { line: msg.clone() })
+                Ok(Err(_)) | Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
+                Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
+            }
+
+            let ready: Vec<WatchTarget> = pending
+                .iter()
+                .filter(|(_, t)| t.elapsed() >= WATCH_DEBOUNCE)
+                .map(|(target, _)| target.clone())
+                .collect();
+            for target in ready {
+                pending.remove(&target);
+
+                let (trigger_file, restart_node, restart_python) = match target {
+                    WatchTarget::Node => ("server.js", true, false),
+                    WatchTarget::Python => ("subtitle_process_backend.py", false, true),
+                    WatchTarget::Config => ("config.json", true, true),
+                };
+                app.emit(
+                    "log-message",
+                    LogMessage {
+                        line: format!(
+                            "[watch] {} changed, reloading affected backend(s).",
+                            trigger_file
+                        ),
+                    },
+                )
                 .ok();
-            state.status = msg;
+
+                let state: State<AppState> = app.state();
+                if restart_node {
+                    let state_clone = Arc::clone(&state.node_server);
+                    let app_clone = app.clone();
+                    tauri::async_runtime::spawn(async move {
+                        stop_process(Arc::clone(&state_clone), "Node", &app_clone).await;
+                        tokio::time::sleep(Duration::from_millis(500)).await;
+                        spawn_process(
+                            "node",
+                            &["server.js"],
+                            Some("./src"),
+                            Some(NODE_READINESS),
+                            app_clone,
+                            state_clone,
+                            "Node",
+                        )
+                        .await;
+                    });
+                }
+                if restart_python {
+                    let state_clone = Arc::clone(&state.python_server);
+                    let app_clone = app.clone();
+                    tauri::async_runtime::spawn(async move {
+                        stop_process(Arc::clone(&state_clone), "Python", &app_clone).await;
+                        tokio::time::sleep(Duration::from_millis(500)).await;
+                        spawn_process(
+                            "python",
+                            &["subtitle_process_backend.py"],
+                            Some("./src"),
+                            Some(PYTHON_READINESS),
+                            app_clone,
+                            state_clone,
+                            "Python",
+                        )
+                        .await;
+                    });
+                }
+            }
         }
-    }
+    });
 }
 
 fn check_command_exists(command: &str) -> bool {
-    let mut cmd = Command::new(command);
+    let mut cmd = std::process::Command::new(command);
     cmd.arg("-version");
     #[cfg(target_os = "windows")]
     {
@@ -542,6 +1929,34 @@ fn get_server_status(state: State<AppState>) -> ServerStatus {
     ServerStatus { node, python }
 }
 
+#[tauri::command]
+fn get_recent_logs(name: String, limit: usize, state: State<AppState>) -> Vec<String> {
+    let target = match name.as_str() {
+        "Node" => &state.node_server,
+        "Python" => &state.python_server,
+        _ => return Vec::new(),
+    };
+    let st = target.lock().unwrap();
+    st.recent_logs
+        .iter()
+        .rev()
+        .take(limit)
+        .rev()
+        .cloned()
+        .collect()
+}
+
+/// 按服务名开关健康检查看门狗，调试时可以临时关掉自动重启
+#[tauri::command]
+fn set_watchdog_enabled(name: String, enabled: bool, state: State<AppState>) {
+    let target = match name.as_str() {
+        "Node" => &state.node_server,
+        "Python" => &state.python_server,
+        _ => return,
+    };
+    target.lock().unwrap().watchdog_enabled = enabled;
+}
+
 #[tauri::command]
 fn start_node_server(app: AppHandle, state: State<AppState>) {
     if is_process_running(&state.node_server) {
@@ -556,40 +1971,44 @@ fn start_node_server(app: AppHandle, state: State<AppState>) {
     }
     let state_clone = Arc::clone(&state.node_server);
     let app_clone = app.clone();
-    thread::spawn(move || {
+    tauri::async_runtime::spawn(async move {
         spawn_process(
             "node",
             &["server.js"],
             Some("./src"),
+            Some(NODE_READINESS),
             app_clone,
             state_clone,
             "Node",
-        );
+        )
+        .await;
     });
 }
 
 #[tauri::command]
 fn stop_node_server(app: AppHandle, state: State<AppState>) {
-    stop_process(Arc::clone(&state.node_server), "Node", &app);
-    app.emit("server-status-changed", serde_json::json!({}))
-        .ok();
+    let state_clone = Arc::clone(&state.node_server);
+    tauri::async_runtime::spawn(async move {
+        stop_process(state_clone, "Node", &app).await;
+    });
 }
 
 #[tauri::command]
 fn restart_node_server(app: AppHandle, state: State<AppState>) {
-    stop_process(Arc::clone(&state.node_server), "Node", &app);
     let state_clone = Arc::clone(&state.node_server);
-    let app_clone = app.clone();
-    thread::spawn(move || {
-        thread::sleep(std::time::Duration::from_millis(500));
+    tauri::async_runtime::spawn(async move {
+        stop_process(Arc::clone(&state_clone), "Node", &app).await;
+        tokio::time::sleep(Duration::from_millis(500)).await;
         spawn_process(
             "node",
             &["server.js"],
             Some("./src"),
-            app_clone,
+            Some(NODE_READINESS),
+            app,
             state_clone,
             "Node",
-        );
+        )
+        .await;
     });
 }
 
@@ -607,43 +2026,65 @@ fn start_python_server(app: AppHandle, state: State<AppState>) {
     }
     let state_clone = Arc::clone(&state.python_server);
     let app_clone = app.clone();
-    thread::spawn(move || {
+    tauri::async_runtime::spawn(async move {
         spawn_process(
             "python",
             &["subtitle_process_backend.py"],
             Some("./src"),
+            Some(PYTHON_READINESS),
             app_clone,
             state_clone,
             "Python",
-        );
+        )
+        .await;
     });
 }
 
 #[tauri::command]
 fn stop_python_server(app: AppHandle, state: State<AppState>) {
-    stop_process(Arc::clone(&state.python_server), "Python", &app);
-    app.emit("server-status-changed", serde_json::json!({}))
-        .ok();
+    let state_clone = Arc::clone(&state.python_server);
+    tauri::async_runtime::spawn(async move {
+        stop_process(state_clone, "Python", &app).await;
+    });
 }
 
 #[tauri::command]
 fn restart_python_server(app: AppHandle, state: State<AppState>) {
-    stop_process(Arc::clone(&state.python_server), "Python", &app);
     let state_clone = Arc::clone(&state.python_server);
-    let app_clone = app.clone();
-    thread::spawn(move || {
-        thread::sleep(std::time::Duration::from_millis(500));
+    tauri::async_runtime::spawn(async move {
+        stop_process(Arc::clone(&state_clone), "Python", &app).await;
+        tokio::time::sleep(Duration::from_millis(500)).await;
         spawn_process(
             "python",
             &["subtitle_process_backend.py"],
             Some("./src"),
-            app_clone,
+            Some(PYTHON_READINESS),
+            app,
             state_clone,
             "Python",
-        );
+        )
+        .await;
     });
 }
 
+/// 按名字手动恢复一个后端，等价于分别调用 `restart_node_server`/`restart_python_server`；
+/// 供前端在 `backend://status` 显示 "gave-up" 之后提供一个统一的"手动恢复"按钮，
+/// 不用再区分具体是哪个服务
+#[tauri::command]
+fn restart_backend(name: String, app: AppHandle, state: State<AppState>) -> Result<(), String> {
+    match name.to_lowercase().as_str() {
+        "node" => {
+            restart_node_server(app, state);
+            Ok(())
+        }
+        "python" => {
+            restart_python_server(app, state);
+            Ok(())
+        }
+        other => Err(format!("Unknown backend: {}", other)),
+    }
+}
+
 #[tauri::command]
 fn load_config() -> Result<Config, String> {
     let config_path = PathBuf::from("./src/config.json");
@@ -683,31 +2124,112 @@ fn load_launcher_settings() -> LauncherSettings {
     }
 }
 
-#[cfg(target_os = "windows")]
-fn apply_autostart(enable: bool) -> Result<(), String> {
-    use winreg::enums::*;
-    use winreg::RegKey;
-    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
-    let run_path = r"Software\Microsoft\Windows\CurrentVersion\Run";
-    let (key, _) = hkcu.create_subkey(run_path).map_err(|e| e.to_string())?;
-    if enable {
-        let exe_path = std::env::current_exe().map_err(|e| e.to_string())?;
-        let exe_str = exe_path.to_string_lossy().to_string();
-        key.set_value("Launcher", &exe_str)
-            .map_err(|e| e.to_string())?;
+/// 启动器自身数据目录（锁文件、窗口状态等都存放在这里）
+fn launcher_data_dir() -> PathBuf {
+    if let Ok(home) = std::env::var("HOME") {
+        PathBuf::from(home).join(".config/webplayer_launcher")
+    } else if let Ok(temp) = std::env::var("TEMP") {
+        PathBuf::from(temp)
     } else {
-        let _ = key.delete_value("Launcher");
+        PathBuf::from(".")
     }
-    Ok(())
 }
 
+fn window_geometry_path() -> PathBuf {
+    launcher_data_dir().join("window_state.json")
+}
+
+fn load_window_geometry() -> Option<WindowGeometry> {
+    let content = fs::read_to_string(window_geometry_path()).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn save_window_geometry(window: &tauri::WebviewWindow) {
+    let geometry = WindowGeometry {
+        x: window.outer_position().map(|p| p.x).unwrap_or(0),
+        y: window.outer_position().map(|p| p.y).unwrap_or(0),
+        width: window.outer_size().map(|s| s.width).unwrap_or(0),
+        height: window.outer_size().map(|s| s.height).unwrap_or(0),
+        maximized: window.is_maximized().unwrap_or(false),
+        fullscreen: window.is_fullscreen().unwrap_or(false),
+    };
+    let _ = fs::create_dir_all(launcher_data_dir());
+    if let Ok(json) = serde_json::to_string_pretty(&geometry) {
+        let _ = fs::write(window_geometry_path(), json);
+    }
+}
+
+/// 若保存的位置落在当前已不存在的显示器上（比如换了一套显示器），
+/// 回退到主显示器的工作区，避免窗口开在屏幕之外看不见
+fn clamp_geometry_to_monitors(window: &tauri::WebviewWindow, geometry: &mut WindowGeometry) {
+    let monitors = match window.available_monitors() {
+        Ok(monitors) if !monitors.is_empty() => monitors,
+        _ => return,
+    };
+
+    let fits = monitors.iter().any(|m| {
+        let pos = m.position();
+        let size = m.size();
+        geometry.x >= pos.x
+            && geometry.y >= pos.y
+            && geometry.x < pos.x + size.width as i32
+            && geometry.y < pos.y + size.height as i32
+    });
+    if fits {
+        return;
+    }
+
+    let fallback = window
+        .primary_monitor()
+        .ok()
+        .flatten()
+        .or_else(|| monitors.into_iter().next());
+    if let Some(monitor) = fallback {
+        let pos = monitor.position();
+        let size = monitor.size();
+        geometry.x = pos.x;
+        geometry.y = pos.y;
+        geometry.width = geometry.width.min(size.width);
+        geometry.height = geometry.height.min(size.height);
+    }
+}
+
+/// 构造指向当前可执行文件的 `AutoLaunch` 句柄；应用名固定为 "Launcher"，这样
+/// Windows 注册表 Run 键/Linux autostart .desktop 里出现的条目能被用户一眼认出
+fn build_auto_launch() -> Result<auto_launch::AutoLaunch, String> {
+    let exe_path = std::env::current_exe().map_err(|e| e.to_string())?;
+    auto_launch::AutoLaunchBuilder::new()
+        .set_app_name("Launcher")
+        .set_app_path(&exe_path.to_string_lossy())
+        .set_args(&[] as &[&str])
+        .build()
+        .map_err(|e| e.to_string())
+}
+
+/// 切换开机自启的 OS 登录项状态并持久化到 launcher 设置；跨平台差异（Windows
+/// 注册表 Run 键/macOS LaunchAgents/Linux autostart .desktop）由 `auto-launch` crate 屏蔽
 #[tauri::command]
-fn save_launcher_settings(settings: LauncherSettings) -> Result<(), String> {
+fn set_launch_on_startup(enabled: bool) -> Result<(), String> {
+    let auto_launch = build_auto_launch()?;
+    if enabled {
+        auto_launch.enable().map_err(|e| e.to_string())?;
+    } else {
+        auto_launch.disable().map_err(|e| e.to_string())?;
+    }
+
+    let mut settings = load_launcher_settings();
+    settings.launch_on_startup = enabled;
+    let json = serde_json::to_string_pretty(&settings).map_err(|e| e.to_string())?;
+    fs::write(launcher_settings_path(), json).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn save_launcher_settings(app: AppHandle, settings: LauncherSettings) -> Result<(), String> {
     let path = launcher_settings_path();
     let json = serde_json::to_string_pretty(&settings).map_err(|e| e.to_string())?;
     fs::write(&path, json).map_err(|e| e.to_string())?;
-    #[cfg(target_os = "windows")]
-    apply_autostart(settings.auto_start_on_boot)?;
+    // 语言可能变了，托盘菜单/提示气泡得用新语言重新生成
+    update_tray_menu(&app);
     Ok(())
 }
 
@@ -783,36 +2305,95 @@ fn run_environment_checks(app: AppHandle) {
     });
 }
 
+/// 对单个目标最多尝试 `NETWORK_CHECK_MAX_ATTEMPTS` 次（失败后短暂退避再重试），
+/// 返回最终状态以及成功尝试的最小/中位延迟
+fn probe_network_target(agent: &ureq::Agent, url: &str) -> (String, u32, Option<u128>, Option<u128>) {
+    let ua = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+    let mut attempts = 0;
+    let mut latencies = Vec::new();
+    let mut last_status = "Failure".to_string();
+
+    for attempt in 0..NETWORK_CHECK_MAX_ATTEMPTS {
+        attempts += 1;
+        let start = std::time::Instant::now();
+        let res = agent.get(url).set("User-Agent", ua).call();
+        last_status = match res {
+            Ok(_) => "Success".to_string(),
+            Err(ureq::Error::Status(code, resp)) => {
+                // Cloudflare often returns 403 or 503 when it detects a bot,
+                // but the site is actually reachable.
+                let is_cf = resp
+                    .header("server")
+                    .map(|s| s.to_lowercase().contains("cloudflare"))
+                    .unwrap_or(false)
+                    || resp.header("cf-ray").is_some();
+                if is_cf || code == 403 || code == 503 {
+                    "Success".to_string()
+                } else {
+                    format!("Failure: HTTP {}", code)
+                }
+            }
+            Err(e) => format!("Failure: {}", e),
+        };
+        if last_status == "Success" {
+            latencies.push(start.elapsed().as_millis());
+            break;
+        }
+        if attempt + 1 < NETWORK_CHECK_MAX_ATTEMPTS {
+            thread::sleep(NETWORK_CHECK_RETRY_BACKOFF);
+        }
+    }
+
+    let min_latency = latencies.iter().min().copied();
+    let median_latency = {
+        let mut sorted = latencies.clone();
+        sorted.sort_unstable();
+        sorted.get(sorted.len() / 2).copied()
+    };
+    (last_status, attempts, min_latency, median_latency)
+}
+
 #[tauri::command]
 fn run_network_checks(app: AppHandle) {
-    let sites: Vec<&str> = vec![
-        "https://musicbrainz.org/",
-        "https://music.163.com/",
-        "https://www.themoviedb.org/",
-        "https://www.javbus.com/",
-        "https://www.jav321.com",
-        "https://javdb.com",
-        "https://www.dmm.co.jp",
-        "https://ads.contents.fc2.com",
-        "https://chii.in",
-        "https://www.getchu.com",
-        "https://hanime1.me",
-    ];
+    let config = load_config().unwrap_or_default();
+    let proxy = config.proxy.clone();
+    let targets = network_check_targets(&config);
 
-    // Set all to Checking
-    for url in &sites {
+    // 代理已启用时，每个目标同时跑直连和走代理两条路径，方便判断到底是
+    // 目标本身不可达还是代理链路有问题
+    let run_both_paths = proxy.enabled;
+
+    for target in &targets {
         app.emit(
             "network-check-update",
             NetworkCheckUpdate {
-                url: url.to_string(),
+                url: target.url.clone(),
+                group: target.group.clone(),
                 status: "Checking".to_string(),
                 latency_ms: None,
+                attempts: 0,
+                median_latency_ms: None,
+                via_proxy: false,
             },
         )
         .ok();
+        if run_both_paths {
+            app.emit(
+                "network-check-update",
+                NetworkCheckUpdate {
+                    url: target.url.clone(),
+                    group: target.group.clone(),
+                    status: "Checking".to_string(),
+                    latency_ms: None,
+                    attempts: 0,
+                    median_latency_ms: None,
+                    via_proxy: true,
+                },
+            )
+            .ok();
+        }
     }
 
-    let urls: Vec<String> = sites.iter().map(|s| s.to_string()).collect();
     let app_clone = app.clone();
 
     thread::spawn(move || {
@@ -820,49 +2401,59 @@ fn run_network_checks(app: AppHandle) {
             .timeout(std::time::Duration::from_secs(10))
             .build();
 
-        let (tx, rx) = std::sync::mpsc::channel::<(String, String, Option<u128>)>();
+        // 只有用户开启了代理才额外建一个走代理的 agent，失败也不影响直连检测
+        let proxy_agent = if proxy.enabled {
+            match ureq::Proxy::new(&proxy.to_proxy_url()) {
+                Ok(p) => Some(
+                    ureq::AgentBuilder::new()
+                        .timeout(std::time::Duration::from_secs(10))
+                        .proxy(p)
+                        .build(),
+                ),
+                Err(_) => None,
+            }
+        } else {
+            None
+        };
+
+        let (tx, rx) = std::sync::mpsc::channel::<(String, String, bool, String, u32, Option<u128>, Option<u128>)>();
+
+        let mut jobs: Vec<(String, String, ureq::Agent, bool)> = targets
+            .iter()
+            .map(|t| (t.url.clone(), t.group.clone(), agent.clone(), false))
+            .collect();
+        if run_both_paths {
+            if let Some(ref proxy_agent) = proxy_agent {
+                jobs.extend(
+                    targets
+                        .iter()
+                        .map(|t| (t.url.clone(), t.group.clone(), proxy_agent.clone(), true)),
+                );
+            }
+        }
 
-        for url in urls {
+        for (url, group, agent, via_proxy) in jobs {
             let tx = tx.clone();
-            let agent = agent.clone();
-            let url_clone = url.clone();
             thread::spawn(move || {
-                let start = std::time::Instant::now();
-                // Use a browser-like User-Agent to avoid simple UA-based blocking
-                let ua = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
-                let res = agent.get(&url_clone).set("User-Agent", ua).call();
-                let status = match res {
-                    Ok(_) => "Success".to_string(),
-                    Err(ureq::Error::Status(code, resp)) => {
-                        // Cloudflare often returns 403 or 503 when it detects a bot,
-                        // but the site is actually reachable.
-                        let is_cf = resp
-                            .header("server")
-                            .map(|s| s.to_lowercase().contains("cloudflare"))
-                            .unwrap_or(false)
-                            || resp.header("cf-ray").is_some();
-                        if is_cf || code == 403 || code == 503 {
-                            "Success".to_string()
-                        } else {
-                            format!("Failure: HTTP {}", code)
-                        }
-                    }
-                    Err(e) => format!("Failure: {}", e),
-                };
-                let elapsed = start.elapsed().as_millis();
-                let _ = tx.send((url_clone, status, Some(elapsed)));
+                let (status, attempts, min_latency, median_latency) =
+                    probe_network_target(&agent, &url);
+                let _ = tx.send((url, group, via_proxy, status, attempts, min_latency, median_latency));
             });
         }
         drop(tx);
 
-        for (url, status, latency_ms) in rx {
+        for (url, group, via_proxy, status, attempts, latency_ms, median_latency_ms) in rx {
             app_clone
                 .emit(
                     "network-check-update",
                     NetworkCheckUpdate {
                         url,
+                        group,
                         status,
                         latency_ms,
+                        attempts,
+                        median_latency_ms,
+                        via_proxy,
                     },
                 )
                 .ok();
@@ -870,13 +2461,149 @@ fn run_network_checks(app: AppHandle) {
     });
 }
 
+#[tauri::command]
+fn test_proxy() -> Result<ProxyTestResult, String> {
+    let proxy = load_config()?.proxy;
+    if !proxy.enabled {
+        return Ok(ProxyTestResult {
+            reachable: false,
+            latency_ms: None,
+            message: "Proxy is not enabled".to_string(),
+        });
+    }
+
+    let proxy_handle = ureq::Proxy::new(&proxy.to_proxy_url()).map_err(|e| e.to_string())?;
+    let agent = ureq::AgentBuilder::new()
+        .timeout(std::time::Duration::from_secs(10))
+        .proxy(proxy_handle)
+        .build();
+
+    let start = std::time::Instant::now();
+    match agent.get("https://musicbrainz.org/").call() {
+        Ok(_) => Ok(ProxyTestResult {
+            reachable: true,
+            latency_ms: Some(start.elapsed().as_millis()),
+            message: "OK".to_string(),
+        }),
+        Err(e) => Ok(ProxyTestResult {
+            reachable: false,
+            latency_ms: None,
+            message: e.to_string(),
+        }),
+    }
+}
+
 // ──────────────────────────── Tray ───────────────────────────────────────────
 
-fn status_display(status: &str) -> &'static str {
+/// 托盘菜单/提示气泡/消息框涉及的语言；与 egui 端完整的 `Language`/`Locale` 体系
+/// 相互独立，这里只覆盖 Tauri 宿主自己画的这几处界面
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TrayLanguage {
+    ZhHans,
+    En,
+    Ja,
+}
+
+impl TrayLanguage {
+    fn from_code(code: &str) -> Self {
+        match code {
+            "en" => Self::En,
+            "ja" => Self::Ja,
+            _ => Self::ZhHans,
+        }
+    }
+
+    fn current() -> Self {
+        Self::from_code(&load_launcher_settings().language)
+    }
+}
+
+/// 托盘/提示气泡/消息框文案的精简查表；`key` 缺失对应语言时回落为 `key` 本身
+fn tr(key: &'static str, lang: TrayLanguage) -> &'static str {
+    use TrayLanguage::*;
+    match (key, lang) {
+        ("status.running", ZhHans) => "运行中",
+        ("status.running", En) => "Running",
+        ("status.running", Ja) => "実行中",
+        ("status.ready", ZhHans) => "就绪",
+        ("status.ready", En) => "Ready",
+        ("status.ready", Ja) => "準備完了",
+        ("status.crashed", ZhHans) => "已崩溃",
+        ("status.crashed", En) => "Crashed",
+        ("status.crashed", Ja) => "クラッシュ",
+        ("status.restarting", ZhHans) => "重启中",
+        ("status.restarting", En) => "Restarting",
+        ("status.restarting", Ja) => "再起動中",
+        ("status.unresponsive", ZhHans) => "未响应",
+        ("status.unresponsive", En) => "Not responding",
+        ("status.unresponsive", Ja) => "応答なし",
+        ("status.stopped", ZhHans) => "已停止",
+        ("status.stopped", En) => "Stopped",
+        ("status.stopped", Ja) => "停止",
+        ("tray.open", ZhHans) => "打开",
+        ("tray.open", En) => "Open",
+        ("tray.open", Ja) => "開く",
+        ("tray.node_label", ZhHans) => "Node 服务：{}",
+        ("tray.node_label", En) => "Node service: {}",
+        ("tray.node_label", Ja) => "Node サービス：{}",
+        ("tray.python_label", ZhHans) => "Python 服务：{}",
+        ("tray.python_label", En) => "Python service: {}",
+        ("tray.python_label", Ja) => "Python サービス：{}",
+        ("tray.node_stop", ZhHans) => "停止 Node 服务",
+        ("tray.node_stop", En) => "Stop Node service",
+        ("tray.node_stop", Ja) => "Node サービスを停止",
+        ("tray.node_start", ZhHans) => "启动 Node 服务",
+        ("tray.node_start", En) => "Start Node service",
+        ("tray.node_start", Ja) => "Node サービスを起動",
+        ("tray.python_stop", ZhHans) => "停止 Python 服务",
+        ("tray.python_stop", En) => "Stop Python service",
+        ("tray.python_stop", Ja) => "Python サービスを停止",
+        ("tray.python_start", ZhHans) => "启动 Python 服务",
+        ("tray.python_start", En) => "Start Python service",
+        ("tray.python_start", Ja) => "Python サービスを起動",
+        ("tray.tunnel_stop", ZhHans) => "停止远程隧道",
+        ("tray.tunnel_stop", En) => "Stop remote tunnel",
+        ("tray.tunnel_stop", Ja) => "リモートトンネルを停止",
+        ("tray.tunnel_start", ZhHans) => "启动远程隧道",
+        ("tray.tunnel_start", En) => "Start remote tunnel",
+        ("tray.tunnel_start", Ja) => "リモートトンネルを起動",
+        ("tray.open_logs", ZhHans) => "打开日志",
+        ("tray.open_logs", En) => "Open logs",
+        ("tray.open_logs", Ja) => "ログを開く",
+        ("tray.quit", ZhHans) => "退出",
+        ("tray.quit", En) => "Quit",
+        ("tray.quit", Ja) => "終了",
+        ("msgbox.title", ZhHans) => "启动器提示",
+        ("msgbox.title", En) => "Launcher notice",
+        ("msgbox.title", Ja) => "ランチャーの通知",
+        ("error.already_running", ZhHans) => "启动器已经在运行中，已将窗口带到前台",
+        ("error.already_running", En) => {
+            "The launcher is already running; its window has been brought to the front"
+        }
+        ("error.already_running", Ja) => "ランチャーはすでに実行中です。ウィンドウを前面に表示しました",
+        ("error.ipc_port_parse", ZhHans) => "无法解析 IPC 端口",
+        ("error.ipc_port_parse", En) => "Failed to parse IPC port",
+        ("error.ipc_port_parse", Ja) => "IPC ポートを解析できませんでした",
+        ("error.lock_file", ZhHans) => "无法创建锁文件",
+        ("error.lock_file", En) => "Failed to create lock file",
+        ("error.lock_file", Ja) => "ロックファイルを作成できませんでした",
+        _ => key,
+    }
+}
+
+fn status_display(status: &str, lang: TrayLanguage) -> &'static str {
     if status == "Running" {
-        "运行中"
+        tr("status.running", lang)
+    } else if status == "Ready" {
+        tr("status.ready", lang)
+    } else if status.starts_with("Crashed") {
+        tr("status.crashed", lang)
+    } else if status.starts_with("Restarting") {
+        tr("status.restarting", lang)
+    } else if status == "Started but not responding" {
+        tr("status.unresponsive", lang)
     } else {
-        "已停止"
+        tr("status.stopped", lang)
     }
 }
 
@@ -884,11 +2611,12 @@ fn update_tray_menu(app: &AppHandle) {
     let state: State<AppState> = app.state();
     let node_status = state.node_server.lock().unwrap().status.clone();
     let python_status = state.python_server.lock().unwrap().status.clone();
+    let lang = TrayLanguage::current();
 
-    let node_running = node_status == "Running";
-    let python_running = python_status == "Running";
+    let node_running = node_status == "Running" || node_status == "Ready";
+    let python_running = python_status == "Running" || python_status == "Ready";
 
-    let open = match MenuItem::with_id(app, "open", "打开", true, None::<&str>) {
+    let open = match MenuItem::with_id(app, "open", tr("tray.open", lang), true, None::<&str>) {
         Ok(v) => v,
         Err(_) => return,
     };
@@ -897,16 +2625,16 @@ fn update_tray_menu(app: &AppHandle) {
         Err(_) => return,
     };
 
-    let node_label = format!("Node 服务：{}", status_display(&node_status));
+    let node_label = tr("tray.node_label", lang).replace("{}", status_display(&node_status, lang));
     let node_status_item =
         match MenuItem::with_id(app, "node_status_display", &node_label, false, None::<&str>) {
             Ok(v) => v,
             Err(_) => return,
         };
     let node_toggle_text = if node_running {
-        "停止 Node 服务"
+        tr("tray.node_stop", lang)
     } else {
-        "启动 Node 服务"
+        tr("tray.node_start", lang)
     };
     let node_toggle_id = if node_running {
         "node_stop"
@@ -924,7 +2652,8 @@ fn update_tray_menu(app: &AppHandle) {
         Err(_) => return,
     };
 
-    let python_label = format!("Python 服务：{}", status_display(&python_status));
+    let python_label =
+        tr("tray.python_label", lang).replace("{}", status_display(&python_status, lang));
     let python_status_item = match MenuItem::with_id(
         app,
         "python_status_display",
@@ -936,9 +2665,9 @@ fn update_tray_menu(app: &AppHandle) {
         Err(_) => return,
     };
     let python_toggle_text = if python_running {
-        "停止 Python 服务"
+        tr("tray.python_stop", lang)
     } else {
-        "启动 Python 服务"
+        tr("tray.python_start", lang)
     };
     let python_toggle_id = if python_running {
         "python_stop"
@@ -960,7 +2689,43 @@ fn update_tray_menu(app: &AppHandle) {
         Ok(v) => v,
         Err(_) => return,
     };
-    let quit = match MenuItem::with_id(app, "quit", "退出", true, None::<&str>) {
+
+    let tunnel_running = state.tunnel.lock().unwrap().child.is_some();
+    let tunnel_toggle_text = if tunnel_running {
+        tr("tray.tunnel_stop", lang)
+    } else {
+        tr("tray.tunnel_start", lang)
+    };
+    let tunnel_toggle_id = if tunnel_running {
+        "tunnel_stop"
+    } else {
+        "tunnel_start"
+    };
+    let tunnel_toggle =
+        match MenuItem::with_id(app, tunnel_toggle_id, tunnel_toggle_text, true, None::<&str>) {
+            Ok(v) => v,
+            Err(_) => return,
+        };
+    let sep_tunnel = match PredefinedMenuItem::separator(app) {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+
+    let open_logs = match MenuItem::with_id(
+        app,
+        "open_logs",
+        tr("tray.open_logs", lang),
+        true,
+        None::<&str>,
+    ) {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+    let sep4 = match PredefinedMenuItem::separator(app) {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+    let quit = match MenuItem::with_id(app, "quit", tr("tray.quit", lang), true, None::<&str>) {
         Ok(v) => v,
         Err(_) => return,
     };
@@ -976,6 +2741,10 @@ fn update_tray_menu(app: &AppHandle) {
             &python_status_item,
             &python_toggle,
             &sep3,
+            &tunnel_toggle,
+            &sep_tunnel,
+            &open_logs,
+            &sep4,
             &quit,
         ],
     ) {
@@ -985,11 +2754,14 @@ fn update_tray_menu(app: &AppHandle) {
     }
 
     // Update tooltip
-    let tooltip = format!(
+    let mut tooltip = format!(
         "Launcher  |  Node: {}  |  Python: {}",
-        status_display(&node_status),
-        status_display(&python_status)
+        status_display(&node_status, lang),
+        status_display(&python_status, lang)
     );
+    if let Some(url) = state.tunnel.lock().unwrap().public_url.as_ref() {
+        tooltip.push_str(&format!("  |  Tunnel: {}", url));
+    }
     if let Some(tray) = app.tray_by_id("main") {
         let _ = tray.set_tooltip(Some(&tooltip));
     }
@@ -1129,6 +2901,8 @@ fn create_template_config() -> Config {
                     dense_subtitles: true,
                 },
             ],
+            proxy: ProxyConfig::default(),
+            network_check_targets: default_network_check_targets(),
     }
 }
 
@@ -1169,133 +2943,134 @@ fn show_message_box(title: &str, message: &str) {
     }
 }
 
-/// Windows上使用命名互斥体进行单实例检测
-#[cfg(target_os = "windows")]
-fn check_single_instance() -> Result<(), String> {
-    let mutex_name = "Global\\WebPlayerLauncher_SingleInstance";
-
-    unsafe {
-        #[link(name = "kernel32")]
-        extern "system" {
-            fn CreateMutexW(
-                lpMutexAttributes: *mut std::ffi::c_void,
-                bInitialOwner: i32,
-                lpName: *const u16,
-            ) -> *mut std::ffi::c_void;
-
-            fn GetLastError() -> u32;
+fn lock_file_path() -> PathBuf {
+    launcher_data_dir().join("launcher.lock")
+}
 
-            fn CloseHandle(hObject: *mut std::ffi::c_void) -> i32;
-        }
+fn ipc_port_path() -> PathBuf {
+    launcher_data_dir().join("launcher.port")
+}
 
-        const ERROR_ALREADY_EXISTS: u32 = 183;
+/// 若锁文件中记录的 PID 对应的进程仍然存活，返回该 PID
+fn running_instance_pid() -> Option<u32> {
+    let content = fs::read_to_string(lock_file_path()).ok()?;
+    let pid: u32 = content.trim().parse().ok()?;
+    let mut system = System::new_all();
+    system.refresh_all();
+    system
+        .process(sysinfo::Pid::from(pid as usize))
+        .map(|_| pid)
+}
 
-        // 将互斥体名称转换为宽字符
-        use std::os::windows::ffi::OsStrExt;
-        let mutex_name_wide: Vec<u16> = std::ffi::OsStr::new(mutex_name)
-            .encode_wide()
-            .chain(Some(0))
-            .collect();
+/// 把当前进程的命令行参数转发给已在运行的实例，让它把窗口带到前台
+fn forward_to_running_instance() -> Result<(), String> {
+    use std::io::Write;
 
-        // 创建互斥体，bInitialOwner=1 表示立即获取所有权
-        let mutex_handle = CreateMutexW(std::ptr::null_mut(), 1, mutex_name_wide.as_ptr());
+    let port: u16 = fs::read_to_string(ipc_port_path())
+        .map_err(|e| e.to_string())?
+        .trim()
+        .parse()
+        .map_err(|_| tr("error.ipc_port_parse", TrayLanguage::current()).to_string())?;
+
+    let mut stream =
+        std::net::TcpStream::connect(("127.0.0.1", port)).map_err(|e| e.to_string())?;
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    stream
+        .write_all(args.join("\n").as_bytes())
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
 
-        if mutex_handle.is_null() {
-            return Err("创建互斥体失败".to_string());
-        }
+/// 监听本地 IPC 端口：后续实例据此把参数转交给当前实例，当前实例收到连接后
+/// 把主窗口带到前台
+fn start_ipc_listener(app: AppHandle) -> Result<(), String> {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").map_err(|e| e.to_string())?;
+    let port = listener.local_addr().map_err(|e| e.to_string())?.port();
+    fs::write(ipc_port_path(), port.to_string()).map_err(|e| e.to_string())?;
 
-        // 检查互斥体是否已存在
-        let error = GetLastError();
-        if error == ERROR_ALREADY_EXISTS {
-            // 互斥体已存在，说明已有其他实例在运行
-            CloseHandle(mutex_handle);
-            return Err("启动器已经在运行中".to_string());
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { continue };
+            use std::io::Read;
+            let mut payload = String::new();
+            if stream.read_to_string(&mut payload).is_err() {
+                continue;
+            }
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
         }
+    });
 
-        // 成功创建互斥体，保存handle以保持互斥体的生命周期
-        // 使用全局静态变量保存handle，防止互斥体被释放
-        static mut MUTEX_HANDLE: *mut std::ffi::c_void = std::ptr::null_mut();
-        MUTEX_HANDLE = mutex_handle;
-
-        Ok(())
-    }
+    Ok(())
 }
 
-/// 非Windows平台的实现
-#[cfg(not(target_os = "windows"))]
+/// 单实例检测：锁文件记录 PID，IPC 端口文件记录转发端口。
+/// 若上一实例仍存活则把参数转发给它并返回错误让调用方退出；
+/// 若锁文件残留但进程已不存在（例如上次崩溃），视为过期锁并接管。
 fn check_single_instance() -> Result<(), String> {
-    use std::io::Write;
-
-    // 在非Windows平台上使用文件锁
-    let lock_dir = if let Ok(home) = std::env::var("HOME") {
-        PathBuf::from(home).join(".config/webplayer_launcher")
-    } else if let Ok(temp) = std::env::var("TEMP") {
-        PathBuf::from(temp)
-    } else {
-        PathBuf::from(".")
-    };
+    let _ = fs::create_dir_all(launcher_data_dir());
 
-    let _ = fs::create_dir_all(&lock_dir);
-
-    let lock_file = lock_dir.join("launcher.lock");
-    let pid = std::process::id();
-
-    // 检查是否存在锁文件，如果存在则检查对应进程是否仍在运行
-    if lock_file.exists() {
-        if let Ok(content) = fs::read_to_string(&lock_file) {
-            if let Ok(old_pid) = content.trim().parse::<u32>() {
-                let mut system = System::new_all();
-                system.refresh_all();
-                if system
-                    .process(sysinfo::Pid::from(old_pid as usize))
-                    .is_some()
-                {
-                    return Err("启动器已经在运行中".to_string());
-                }
-            }
+    if running_instance_pid().is_some() {
+        if forward_to_running_instance().is_ok() {
+            return Err(tr("error.already_running", TrayLanguage::current()).to_string());
         }
-        let _ = fs::remove_file(&lock_file);
+        // 锁文件中的 PID 存活，但 IPC 端口连不上（可能正在重启），当作过期锁处理
     }
 
-    // 创建新的锁文件
-    if let Ok(mut file) = fs::File::create(&lock_file) {
-        let _ = writeln!(file, "{}", pid);
-        Ok(())
-    } else {
-        Err("无法创建锁文件".to_string())
-    }
+    let _ = fs::remove_file(lock_file_path());
+    let _ = fs::remove_file(ipc_port_path());
+
+    fs::write(lock_file_path(), std::process::id().to_string())
+        .map_err(|_| tr("error.lock_file", TrayLanguage::current()).to_string())
 }
 
-/// 清理启动器锁文件（仅非Windows平台需要）
-#[cfg(not(target_os = "windows"))]
+/// 清理启动器锁文件和 IPC 端口文件
 fn cleanup_lock_file() {
-    let lock_dir = if let Ok(home) = std::env::var("HOME") {
-        PathBuf::from(home).join(".config/webplayer_launcher")
-    } else if let Ok(temp) = std::env::var("TEMP") {
-        PathBuf::from(temp)
-    } else {
-        PathBuf::from(".")
-    };
-
-    let lock_file = lock_dir.join("launcher.lock");
-    let _ = fs::remove_file(&lock_file);
+    let _ = fs::remove_file(lock_file_path());
+    let _ = fs::remove_file(ipc_port_path());
+    sentry::add_breadcrumb(sentry::Breadcrumb {
+        category: Some("lifecycle".to_string()),
+        message: Some("Lock file cleaned up on exit".to_string()),
+        level: sentry::Level::Info,
+        ..Default::default()
+    });
 }
 
-/// Windows平台互斥体无需手动清理（进程退出时自动释放）
-#[cfg(target_os = "windows")]
-fn cleanup_lock_file() {
-    // 互斥体会在进程退出时自动被操作系统释放
+/// 读取 launcher 设置并在启用时初始化 Sentry，持有返回的 guard 以保证程序生命周期内
+/// 崩溃上报通道不被提前析构；DSN 为空或 `telemetry_enabled` 为 false 时完全跳过初始化，
+/// 不产生任何流量
+fn init_telemetry() -> Option<sentry::ClientInitGuard> {
+    let settings = load_launcher_settings();
+    if !settings.telemetry_enabled || settings.telemetry_dsn.trim().is_empty() {
+        return None;
+    }
+    let guard = sentry::init((
+        settings.telemetry_dsn.as_str(),
+        sentry::ClientOptions {
+            release: Some(std::borrow::Cow::Borrowed(env!("CARGO_PKG_VERSION"))),
+            attach_stacktrace: true,
+            ..Default::default()
+        }
+        .add_integration(sentry::integrations::panic::PanicIntegration::default()),
+    ));
+    Some(guard)
 }
 
 pub fn run() {
+    // 在创建 Tauri Builder 之前初始化遥测，这样启动阶段的 panic 也能被捕获；
+    // guard 绑定到 `_sentry_guard`（下划线前缀避免 clippy 误报未使用，但名字仍需存活到 run 结束）
+    let _sentry_guard = init_telemetry();
+
     // 检查单实例
     if let Err(error_msg) = check_single_instance() {
         // 显示错误信息并退出
         #[cfg(target_os = "windows")]
         {
             // Windows下使用MessageBox显示错误
-            let title = "启动器提示";
-            show_message_box(&title, &error_msg);
+            let title = tr("msgbox.title", TrayLanguage::current());
+            show_message_box(title, &error_msg);
         }
 
         #[cfg(not(target_os = "windows"))]
@@ -1317,18 +3092,28 @@ pub fn run() {
         .manage(AppState::new())
         .invoke_handler(tauri::generate_handler![
             get_server_status,
+            get_recent_logs,
+            set_watchdog_enabled,
             start_node_server,
             stop_node_server,
             restart_node_server,
             start_python_server,
             stop_python_server,
             restart_python_server,
+            restart_backend,
+            send_backend_command,
+            get_log_files,
+            read_log_tail,
+            start_tunnel,
+            stop_tunnel,
             load_config,
             save_config,
             load_launcher_settings,
             save_launcher_settings,
+            set_launch_on_startup,
             run_environment_checks,
             run_network_checks,
+            test_proxy,
         ])
         .setup(|app| {
             // Build initial tray icon (no menu yet; update_tray_menu will set it)
@@ -1347,52 +3132,98 @@ pub fn run() {
                         if !is_process_running(&state.node_server) {
                             let state_clone = Arc::clone(&state.node_server);
                             let app_clone = app.clone();
-                            thread::spawn(move || {
+                            tauri::async_runtime::spawn(async move {
                                 spawn_process(
                                     "node",
                                     &["server.js"],
                                     Some("./src"),
+                                    Some(NODE_READINESS),
                                     app_clone,
                                     state_clone,
                                     "Node",
-                                );
+                                )
+                                .await;
                             });
                         }
                     }
                     "node_stop" => {
                         let state: State<AppState> = app.state();
-                        stop_process(Arc::clone(&state.node_server), "Node", app);
-                        app.emit("server-status-changed", serde_json::json!({}))
-                            .ok();
+                        let state_clone = Arc::clone(&state.node_server);
+                        let app_clone = app.clone();
+                        tauri::async_runtime::spawn(async move {
+                            stop_process(state_clone, "Node", &app_clone).await;
+                        });
                     }
                     "python_start" => {
                         let state: State<AppState> = app.state();
                         if !is_process_running(&state.python_server) {
                             let state_clone = Arc::clone(&state.python_server);
                             let app_clone = app.clone();
-                            thread::spawn(move || {
+                            tauri::async_runtime::spawn(async move {
                                 spawn_process(
                                     "python",
                                     &["subtitle_process_backend.py"],
                                     Some("./src"),
+                                    Some(PYTHON_READINESS),
                                     app_clone,
                                     state_clone,
                                     "Python",
-                                );
+                                )
+                                .await;
                             });
                         }
                     }
                     "python_stop" => {
                         let state: State<AppState> = app.state();
-                        stop_process(Arc::clone(&state.python_server), "Python", app);
-                        app.emit("server-status-changed", serde_json::json!({}))
-                            .ok();
+                        let state_clone = Arc::clone(&state.python_server);
+                        let app_clone = app.clone();
+                        tauri::async_runtime::spawn(async move {
+                            stop_process(state_clone, "Python", &app_clone).await;
+                        });
+                    }
+                    "tunnel_start" => {
+                        let state: State<AppState> = app.state();
+                        if state.tunnel.lock().unwrap().child.is_none() {
+                            let app_clone = app.clone();
+                            tauri::async_runtime::spawn(async move {
+                                let state: State<AppState> = app_clone.state();
+                                start_tunnel(app_clone.clone(), state).await.ok();
+                            });
+                        }
+                    }
+                    "tunnel_stop" => {
+                        let app_clone = app.clone();
+                        tauri::async_runtime::spawn(async move {
+                            let state: State<AppState> = app_clone.state();
+                            stop_tunnel(app_clone.clone(), state).await.ok();
+                        });
+                    }
+                    "open_logs" => {
+                        let dir = logs_dir();
+                        if fs::create_dir_all(&dir).is_ok() {
+                            #[cfg(target_os = "windows")]
+                            let result = std::process::Command::new("explorer").arg(&dir).spawn();
+                            #[cfg(target_os = "macos")]
+                            let result = std::process::Command::new("open").arg(&dir).spawn();
+                            #[cfg(all(unix, not(target_os = "macos")))]
+                            let result = std::process::Command::new("xdg-open").arg(&dir).spawn();
+                            let _ = result;
+                        }
                     }
                     "quit" => {
                         let state: State<AppState> = app.state();
-                        stop_process(Arc::clone(&state.node_server), "Node", app);
-                        stop_process(Arc::clone(&state.python_server), "Python", app);
-                        app.exit(0);
+                        let node_state = Arc::clone(&state.node_server);
+                        let python_state = Arc::clone(&state.python_server);
+                        let app_clone = app.clone();
+                        tauri::async_runtime::spawn(async move {
+                            let tunnel_state: State<AppState> = app_clone.state();
+                            tokio::join!(
+                                stop_process(node_state, "Node", &app_clone),
+                                stop_process(python_state, "Python", &app_clone),
+                                stop_tunnel(app_clone.clone(), tunnel_state),
+                            );
+                            app_clone.exit(0);
+                        });
                     }
                     _ => {}
                 })
@@ -1421,6 +3252,32 @@ pub fn run() {
             // Set initial tray menu
             update_tray_menu(app.handle());
 
+            // Start listening for args forwarded by a second launch attempt
+            if let Err(e) = start_ipc_listener(app.handle().clone()) {
+                eprintln!("Failed to start single-instance IPC listener: {}", e);
+            }
+
+            // Restore window geometry before applying start_minimized, so a
+            // minimized start still remembers where to reappear later.
+            if let Some(window) = app.get_webview_window("main") {
+                if let Some(mut geometry) = load_window_geometry() {
+                    clamp_geometry_to_monitors(&window, &mut geometry);
+                    let _ = window.set_position(tauri::PhysicalPosition::new(
+                        geometry.x,
+                        geometry.y,
+                    ));
+                    let _ = window.set_size(tauri::PhysicalSize::new(
+                        geometry.width,
+                        geometry.height,
+                    ));
+                    if geometry.fullscreen {
+                        let _ = window.set_fullscreen(true);
+                    } else if geometry.maximized {
+                        let _ = window.maximize();
+                    }
+                }
+            }
+
             // Apply launcher settings on startup
             let startup_settings = load_launcher_settings();
             if startup_settings.start_minimized {
@@ -1428,34 +3285,55 @@ pub fn run() {
                     let _ = window.hide();
                 }
             }
+            // 用户可能在 OS 的登录项设置里手动改过（例如直接从系统设置移除），
+            // 以持久化的 launcher 设置为准，静默纠正漂移
+            if let Ok(auto_launch) = build_auto_launch() {
+                let os_enabled = auto_launch
+                    .is_enabled()
+                    .unwrap_or(startup_settings.launch_on_startup);
+                if os_enabled != startup_settings.launch_on_startup {
+                    let _ = if startup_settings.launch_on_startup {
+                        auto_launch.enable()
+                    } else {
+                        auto_launch.disable()
+                    };
+                }
+            }
+            if startup_settings.watch_and_reload {
+                spawn_backend_watcher(app.handle().clone());
+            }
             if startup_settings.auto_start_node {
                 let state: State<AppState> = app.state();
                 let state_clone = Arc::clone(&state.node_server);
                 let app_clone = app.handle().clone();
-                thread::spawn(move || {
+                tauri::async_runtime::spawn(async move {
                     spawn_process(
                         "node",
                         &["server.js"],
                         Some("./src"),
+                        Some(NODE_READINESS),
                         app_clone,
                         state_clone,
                         "Node",
-                    );
+                    )
+                    .await;
                 });
             }
             if startup_settings.auto_start_python {
                 let state: State<AppState> = app.state();
                 let state_clone = Arc::clone(&state.python_server);
                 let app_clone = app.handle().clone();
-                thread::spawn(move || {
+                tauri::async_runtime::spawn(async move {
                     spawn_process(
                         "python",
                         &["subtitle_process_backend.py"],
                         Some("./src"),
+                        Some(PYTHON_READINESS),
                         app_clone,
                         state_clone,
                         "Python",
-                    );
+                    )
+                    .await;
                 });
             }
 
@@ -1471,13 +3349,22 @@ pub fn run() {
             } => {
                 api.prevent_close();
                 if let Some(window) = app_handle.get_webview_window(&label) {
+                    save_window_geometry(&window);
                     let _ = window.hide();
                 }
             }
             tauri::RunEvent::Exit => {
                 let state: State<AppState> = app_handle.state();
-                stop_process(Arc::clone(&state.node_server), "Node", app_handle);
-                stop_process(Arc::clone(&state.python_server), "Python", app_handle);
+                tauri::async_runtime::block_on(async {
+                    tokio::join!(
+                        stop_process(Arc::clone(&state.node_server), "Node", app_handle),
+                        stop_process(Arc::clone(&state.python_server), "Python", app_handle),
+                    );
+                });
+
+                if let Some(window) = app_handle.get_webview_window("main") {
+                    save_window_geometry(&window);
+                }
 
                 // 清理锁文件
                 cleanup_lock_file();
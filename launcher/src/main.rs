@@ -10,13 +10,334 @@ use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Stdio};
 use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::{Duration, Instant};
 use sysinfo::System;
 
+/// 自动重启退避策略：初始等待、封顶等待、视为"已稳定"的存活时长、最大连续重试次数
+const INITIAL_RESTART_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_RESTART_BACKOFF: Duration = Duration::from_secs(30);
+const STABLE_UPTIME_THRESHOLD: Duration = Duration::from_secs(10);
+const MAX_RESTART_ATTEMPTS: u32 = 10;
+
+/// 优雅关闭时，发出终止信号后等待进程组自行退出的宽限期
+const GRACEFUL_SHUTDOWN_GRACE: Duration = Duration::from_secs(5);
+
+/// 文件监听防抖窗口：编辑器保存引发的一连串变更事件合并为一次重载/重启
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// 启动阶段就绪探测：每次尝试的超时、两次尝试之间的间隔、放弃前的最大尝试次数
+const READINESS_PROBE_TIMEOUT: Duration = Duration::from_millis(500);
+const READINESS_PROBE_INTERVAL: Duration = Duration::from_millis(300);
+const READINESS_MAX_ATTEMPTS: u32 = 30;
+/// 就绪之后转入的常态健康检查：探测间隔、判定为"假死"所需的连续失败次数
+const HEALTH_PROBE_INTERVAL: Duration = Duration::from_secs(5);
+const HEALTH_PROBE_FAILURE_THRESHOLD: u32 = 3;
+
+/// Windows 下用于绑定子进程及其后代的 Job Object 句柄
+/// （Job Object 句柄在系统层面线程安全，可以跨线程持有）
+#[cfg(target_os = "windows")]
+struct JobHandle(windows_sys::Win32::Foundation::HANDLE);
+
+#[cfg(target_os = "windows")]
+unsafe impl Send for JobHandle {}
+
+#[cfg(target_os = "windows")]
+impl Drop for JobHandle {
+    fn drop(&mut self) {
+        unsafe {
+            windows_sys::Win32::Foundation::CloseHandle(self.0);
+        }
+    }
+}
+
+/// 单个服务的滚动日志落盘参数（目录、单文件上限、保留的历史文件数），来自 `Config`
+#[derive(Clone, Debug)]
+struct LogFileConfig {
+    dir: PathBuf,
+    max_size_bytes: u64,
+    max_backups: u32,
+}
+
+/// 把某个服务的 stdout/stderr 行持续追加写入磁盘文件，超过 `max_size_bytes` 就滚动
+/// （`<name>.log.1` 为最近一次滚动，`<name>.log.<max_backups>` 为最旧，超出的直接丢弃）
+struct RotatingLogFile {
+    dir: PathBuf,
+    base_name: String,
+    max_size_bytes: u64,
+    max_backups: u32,
+    file: fs::File,
+    current_size: u64,
+}
+
+impl RotatingLogFile {
+    fn open(config: &LogFileConfig, base_name: &str) -> std::io::Result<Self> {
+        fs::create_dir_all(&config.dir)?;
+        let path = config.dir.join(format!("{}.log", base_name));
+        let file = fs::OpenOptions::new().create(true).append(true).open(&path)?;
+        let current_size = file.metadata()?.len();
+        Ok(Self {
+            dir: config.dir.clone(),
+            base_name: base_name.to_string(),
+            max_size_bytes: config.max_size_bytes,
+            max_backups: config.max_backups,
+            file,
+            current_size,
+        })
+    }
+
+    fn active_path(&self) -> PathBuf {
+        self.dir.join(format!("{}.log", self.base_name))
+    }
+
+    fn write_line(&mut self, line: &str) {
+        use std::io::Write;
+        let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f");
+        let bytes = format!("[{}] {}\n", timestamp, line);
+        if self.file.write_all(bytes.as_bytes()).is_ok() {
+            self.current_size += bytes.len() as u64;
+        }
+        if self.current_size >= self.max_size_bytes {
+            self.rotate();
+        }
+    }
+
+    /// 无条件滚动到一个新的会话文件，不等到达到 `max_size_bytes`；
+    /// 用于用户主动触发“清空日志”/“重启”时保留旧会话而非静默丢弃
+    fn start_new_session(&mut self) {
+        self.rotate();
+    }
+
+    fn rotate(&mut self) {
+        if self.max_backups == 0 {
+            if let Ok(f) = fs::OpenOptions::new()
+                .create(true)
+                .write(true)
+                .truncate(true)
+                .open(self.active_path())
+            {
+                self.file = f;
+                self.current_size = 0;
+            }
+            return;
+        }
+
+        // 超出保留数量的最旧备份直接删除，再把其余备份依次后移一位
+        let oldest = self
+            .dir
+            .join(format!("{}.log.{}", self.base_name, self.max_backups));
+        fs::remove_file(&oldest).ok();
+        for i in (1..self.max_backups).rev() {
+            let from = self.dir.join(format!("{}.log.{}", self.base_name, i));
+            let to = self.dir.join(format!("{}.log.{}", self.base_name, i + 1));
+            if from.exists() {
+                fs::rename(&from, &to).ok();
+            }
+        }
+
+        let active_path = self.active_path();
+        let backup1 = self.dir.join(format!("{}.log.1", self.base_name));
+        fs::rename(&active_path, &backup1).ok();
+
+        if let Ok(f) = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&active_path)
+        {
+            self.file = f;
+            self.current_size = 0;
+        }
+    }
+}
+
 // --- Process Management ---
-#[derive(Default)]
 struct ProcessState {
     child: Option<Child>,
     status: String,
+    /// 是否为该服务启用崩溃自动重启（由用户在 UI 中切换）
+    auto_restart: bool,
+    /// 是否在监听到其源文件/config.json 变化时自动重启该服务（由用户在 UI 中切换）
+    auto_reload: bool,
+    /// 连续重启次数；进程稳定运行超过 `STABLE_UPTIME_THRESHOLD` 后清零
+    restart_count: u32,
+    /// 下一次自动重启前的退避等待时间，每次失败翻倍，封顶 `MAX_RESTART_BACKOFF`
+    next_backoff: Duration,
+    /// 本次启动的时间点，用于判断是否已稳定运行
+    started_at: Option<Instant>,
+    /// Windows 下该子进程所属的 Job Object，关闭句柄会终止整个进程树
+    #[cfg(target_os = "windows")]
+    job_handle: Option<JobHandle>,
+    /// 当前进程实例的滚动日志文件句柄，供"清空日志"/"重启"等操作触发新会话
+    log_file: Option<Arc<Mutex<RotatingLogFile>>>,
+    /// `stop_process` 在接管 `child` 之前置位，标记这次退出是用户主动要求的，而非崩溃；
+    /// `spawn_process` 成功拉起时清零；监控线程在退避等待结束后会重新检查它，
+    /// 用户在等待期间点了 Stop 就放弃这次自动重启
+    shutdown_requested: bool,
+}
+
+impl Default for ProcessState {
+    fn default() -> Self {
+        Self {
+            child: None,
+            status: String::new(),
+            auto_restart: false,
+            auto_reload: false,
+            restart_count: 0,
+            next_backoff: INITIAL_RESTART_BACKOFF,
+            started_at: None,
+            #[cfg(target_os = "windows")]
+            job_handle: None,
+            log_file: None,
+            shutdown_requested: false,
+        }
+    }
+}
+
+/// 在服务状态发生变化（启动、崩溃、重启耗尽、用户停止……）时弹出系统桌面通知，
+/// 由调用方根据 `Config::notifications_enabled` 决定是否启用
+fn notify_service_event(enabled: bool, summary: &str, body: &str) {
+    if !enabled {
+        return;
+    }
+    if let Err(e) = notify_rust::Notification::new()
+        .summary(summary)
+        .body(body)
+        .show()
+    {
+        eprintln!("Failed to show desktop notification: {}", e);
+    }
+}
+
+/// 被监听文件发生变化时应触发的动作
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum WatchTarget {
+    /// 携带 `ServiceConfig::id`，用于在 `services` 列表中定位对应的运行时状态
+    Service(String),
+    Config,
+}
+
+/// 监听 `paths` 中列出的文件，变化经过 `WATCH_DEBOUNCE` 防抖后，把对应的 `WatchTarget`
+/// 发送给 `watch_sender`；防抖与转发在独立线程中进行，watcher 本身随该线程存活
+fn spawn_file_watcher(
+    paths: Vec<(PathBuf, WatchTarget)>,
+    watch_sender: crossbeam_channel::Sender<WatchTarget>,
+    log_sender: crossbeam_channel::Sender<String>,
+) {
+    use notify::{RecursiveMode, Watcher};
+
+    let (raw_tx, raw_rx) = std::sync::mpsc::channel();
+    let mut watcher = match notify::recommended_watcher(raw_tx) {
+        Ok(w) => w,
+        Err(e) => {
+            log_sender
+                .send(format!("[watch] Failed to create file watcher: {}", e))
+                .ok();
+            return;
+        }
+    };
+
+    for (path, _) in &paths {
+        if let Err(e) = watcher.watch(path, RecursiveMode::NonRecursive) {
+            log_sender
+                .send(format!("[watch] Failed to watch {}: {}", path.display(), e))
+                .ok();
+        }
+    }
+
+    thread::spawn(move || {
+        // 把 watcher 移进线程里存活，离开作用域被 drop 会立即停止监听
+        let _watcher = watcher;
+        let mut pending: std::collections::HashMap<WatchTarget, Instant> =
+            std::collections::HashMap::new();
+
+        loop {
+            match raw_rx.recv_timeout(Duration::from_millis(100)) {
+                Ok(Ok(event)) => {
+                    if matches!(
+                        event.kind,
+                        notify::EventKind::Modify(_) | notify::EventKind::Create(_)
+                    ) {
+                        for event_path in &event.paths {
+                            for (watched_path, target) in &paths {
+                                if event_path == watched_path {
+                                    pending.insert(target.clone(), Instant::now());
+                                }
+                            }
+                        }
+                    }
+                }
+                Ok(Err(_)) | Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
+                Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
+            }
+
+            let ready: Vec<WatchTarget> = pending
+                .iter()
+                .filter(|(_, t)| t.elapsed() >= WATCH_DEBOUNCE)
+                .map(|(target, _)| target.clone())
+                .collect();
+            for target in ready {
+                pending.remove(&target);
+                watch_sender.send(target).ok();
+            }
+        }
+    });
+}
+
+/// Unix: 向子进程所在的整个进程组发送信号（负 PID 表示目标为进程组）
+#[cfg(unix)]
+fn signal_process_group(pgid: i32, signal: i32) {
+    unsafe {
+        libc::kill(-pgid, signal);
+    }
+}
+
+/// 尝试在 `timeout` 内与 `host:port` 建立一次 TCP 连接，用来判断服务是否已经在监听
+fn tcp_probe(host: &str, port: u16, timeout: Duration) -> bool {
+    use std::net::ToSocketAddrs;
+
+    let addr = match (host, port).to_socket_addrs() {
+        Ok(mut addrs) => addrs.next(),
+        Err(_) => None,
+    };
+    match addr {
+        Some(addr) => std::net::TcpStream::connect_timeout(&addr, timeout).is_ok(),
+        None => false,
+    }
+}
+
+/// Windows: 创建一个会在句柄关闭时终止全部成员进程的 Job Object，并把子进程加入其中，
+/// 以此代替进程组语义，保证 `taskkill`/句柄关闭能清理整棵子进程树
+#[cfg(target_os = "windows")]
+fn assign_to_new_job_object(child: &Child) -> Option<JobHandle> {
+    use std::os::windows::io::AsRawHandle;
+    use windows_sys::Win32::System::JobObjects::{
+        AssignProcessToJobObject, CreateJobObjectW, JobObjectExtendedLimitInformation,
+        SetInformationJobObject, JOBOBJECT_EXTENDED_LIMIT_INFORMATION,
+        JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE,
+    };
+
+    unsafe {
+        let job = CreateJobObjectW(std::ptr::null(), std::ptr::null());
+        if job == 0 {
+            return None;
+        }
+
+        let mut info: JOBOBJECT_EXTENDED_LIMIT_INFORMATION = std::mem::zeroed();
+        info.BasicLimitInformation.LimitFlags = JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
+        SetInformationJobObject(
+            job,
+            JobObjectExtendedLimitInformation,
+            &info as *const _ as *const _,
+            std::mem::size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+        );
+
+        let process_handle = child.as_raw_handle() as windows_sys::Win32::Foundation::HANDLE;
+        if AssignProcessToJobObject(job, process_handle) == 0 {
+            windows_sys::Win32::Foundation::CloseHandle(job);
+            return None;
+        }
+
+        Some(JobHandle(job))
+    }
 }
 
 // --- Data Structures for config.json ---
@@ -126,6 +447,46 @@ struct Model {
     prompt_template: String,
 }
 
+/// 单个受管理子进程的声明式定义：程序、参数、工作目录、环境变量与就绪探测目标，
+/// 取代过去散落在 `spawn_process` 调用处的 node/python 专用字面量，
+/// 让用户无需重新编译即可新增第三个服务或切换 `python` 为 `python3`
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct ServiceConfig {
+    /// 内部标识，用于匹配运行时状态与 `WatchTarget`，同时作为滚动日志文件名
+    id: String,
+    /// UI 中展示的名称
+    display_name: String,
+    program: String,
+    #[serde(default)]
+    args: Vec<String>,
+    #[serde(default)]
+    working_dir: Option<String>,
+    /// 注入子进程的额外环境变量（如 `NODE_ENV`、`PYTHONPATH`）
+    #[serde(default)]
+    env: std::collections::HashMap<String, String>,
+    /// 触发自动重载所监听的源文件路径；留空则不监听该服务的源码变化
+    #[serde(default)]
+    watch_path: Option<String>,
+    /// 就绪/健康探测（TCP connect）的目标地址
+    readiness_host: String,
+    /// 就绪/健康探测的目标端口
+    readiness_port: u16,
+    /// 该服务崩溃后是否默认启用自动重启（带指数退避），新建的运行时状态据此初始化
+    #[serde(default = "default_auto_restart")]
+    auto_restart: bool,
+    /// 依赖清单文件（如 `requirements.txt`/`package.json`），相对路径按 `working_dir` 解析；
+    /// 留空则跳过依赖同步
+    #[serde(default)]
+    dependency_manifest: Option<String>,
+    /// 依赖清单内容哈希变化时要执行的安装命令（如 `["pip", "install", "-r", "requirements.txt"]`）
+    #[serde(default)]
+    dependency_install_cmd: Option<Vec<String>>,
+}
+
+fn default_auto_restart() -> bool {
+    true
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 struct Config {
     api_keys: ApiKeys,
@@ -133,6 +494,98 @@ struct Config {
     models: Vec<Model>,
     #[serde(default)]
     transcriber_models: Vec<TranscriberModel>,
+    /// 服务状态变化（启动/崩溃/重启耗尽/手动停止）时是否弹出系统桌面通知
+    #[serde(default = "default_notifications_enabled")]
+    notifications_enabled: bool,
+    /// 受管理服务的声明式定义列表，驱动启动器 UI 与 `spawn_process`
+    #[serde(default = "default_services")]
+    services: Vec<ServiceConfig>,
+    /// 各服务滚动日志文件的落盘目录
+    #[serde(default = "default_log_dir")]
+    log_dir: String,
+    /// 单个日志文件的滚动阈值（MB），超过后滚动为 `.log.1`
+    #[serde(default = "default_log_max_size_mb")]
+    log_max_size_mb: u64,
+    /// 每个服务保留的历史日志文件数，超出的最旧文件会被删除
+    #[serde(default = "default_log_max_backups")]
+    log_max_backups: u32,
+    /// 当前激活的 UI 主题（深色/浅色/高对比度），同时驱动日志着色
+    #[serde(default)]
+    theme: ThemeVariant,
+    /// 当前激活的界面语言，驱动 `tr()` 查表
+    #[serde(default)]
+    ui_language: Language,
+    /// 出站 HTTP 请求（网络检查、模型对比等）使用的代理配置
+    #[serde(default)]
+    proxy: ProxyConfig,
+}
+
+/// 出站 ureq 请求的代理配置；`url` 形如 `host:port`，留空或 `enabled = false` 时不使用代理
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+struct ProxyConfig {
+    #[serde(default)]
+    enabled: bool,
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    username: String,
+    #[serde(default)]
+    password: String,
+}
+
+fn default_notifications_enabled() -> bool {
+    true
+}
+
+fn default_log_dir() -> String {
+    "./logs".to_string()
+}
+
+fn default_log_max_size_mb() -> u64 {
+    5
+}
+
+fn default_log_max_backups() -> u32 {
+    5
+}
+
+/// Node/Python 两个服务的出厂默认定义，保持与重构前硬编码的启动参数一致
+fn default_services() -> Vec<ServiceConfig> {
+    vec![
+        ServiceConfig {
+            id: "node".to_string(),
+            display_name: "Node Server".to_string(),
+            program: "node".to_string(),
+            args: vec!["server.js".to_string()],
+            working_dir: Some("./src".to_string()),
+            env: std::collections::HashMap::new(),
+            watch_path: Some("./src/server.js".to_string()),
+            readiness_host: "127.0.0.1".to_string(),
+            readiness_port: 3000,
+            auto_restart: default_auto_restart(),
+            dependency_manifest: Some("package.json".to_string()),
+            dependency_install_cmd: Some(vec!["npm".to_string(), "install".to_string()]),
+        },
+        ServiceConfig {
+            id: "python".to_string(),
+            display_name: "Python Backend".to_string(),
+            program: "python".to_string(),
+            args: vec!["subtitle_process_backend.py".to_string()],
+            working_dir: Some("./src".to_string()),
+            env: std::collections::HashMap::new(),
+            watch_path: Some("./src/subtitle_process_backend.py".to_string()),
+            readiness_host: "127.0.0.1".to_string(),
+            readiness_port: 8000,
+            auto_restart: default_auto_restart(),
+            dependency_manifest: Some("requirements.txt".to_string()),
+            dependency_install_cmd: Some(vec![
+                "pip".to_string(),
+                "install".to_string(),
+                "-r".to_string(),
+                "requirements.txt".to_string(),
+            ]),
+        },
+    ]
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -167,6 +620,171 @@ impl Default for TranscriberModel {
     }
 }
 
+// --- Theme Subsystem ---
+
+/// 可选的 UI 主题变体，持久化在 `Config` 中并驱动 egui 视觉样式与日志着色
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum ThemeVariant {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+}
+
+impl ThemeVariant {
+    fn label(&self) -> &'static str {
+        match self {
+            ThemeVariant::Dark => "Dark",
+            ThemeVariant::Light => "Light",
+            ThemeVariant::HighContrast => "High Contrast",
+        }
+    }
+
+    const ALL: [ThemeVariant; 3] = [
+        ThemeVariant::Dark,
+        ThemeVariant::Light,
+        ThemeVariant::HighContrast,
+    ];
+}
+
+/// 日志高亮等跨界面场景使用的一组语义色，随主题变化；与 `egui::Visuals` 一起构成完整主题
+#[derive(Debug, Clone, Copy)]
+struct ThemePalette {
+    accent: egui::Color32,
+    success: egui::Color32,
+    warning: egui::Color32,
+    error: egui::Color32,
+    monospace_bg: egui::Color32,
+}
+
+/// 把 `ThemeVariant` 解析为实际可用的视觉样式与语义调色板
+trait ThemeDef {
+    fn visuals(&self) -> egui::Visuals;
+    fn palette(&self) -> ThemePalette;
+}
+
+impl ThemeDef for ThemeVariant {
+    fn visuals(&self) -> egui::Visuals {
+        match self {
+            ThemeVariant::Dark => egui::Visuals::dark(),
+            ThemeVariant::Light => egui::Visuals::light(),
+            ThemeVariant::HighContrast => {
+                let mut visuals = egui::Visuals::dark();
+                visuals.override_text_color = Some(egui::Color32::WHITE);
+                visuals.extreme_bg_color = egui::Color32::BLACK;
+                visuals.widgets.noninteractive.bg_fill = egui::Color32::BLACK;
+                visuals.widgets.noninteractive.fg_stroke.color = egui::Color32::WHITE;
+                visuals
+            }
+        }
+    }
+
+    fn palette(&self) -> ThemePalette {
+        match self {
+            ThemeVariant::Dark => ThemePalette {
+                accent: egui::Color32::from_rgb(100, 180, 255),
+                success: egui::Color32::from_rgb(85, 255, 85),
+                warning: egui::Color32::from_rgb(255, 255, 85),
+                error: egui::Color32::from_rgb(255, 85, 85),
+                monospace_bg: egui::Color32::from_rgb(30, 30, 30),
+            },
+            ThemeVariant::Light => ThemePalette {
+                accent: egui::Color32::from_rgb(0, 90, 200),
+                success: egui::Color32::from_rgb(0, 130, 0),
+                warning: egui::Color32::from_rgb(170, 110, 0),
+                error: egui::Color32::from_rgb(190, 0, 0),
+                monospace_bg: egui::Color32::from_rgb(245, 245, 245),
+            },
+            ThemeVariant::HighContrast => ThemePalette {
+                accent: egui::Color32::from_rgb(255, 255, 0),
+                success: egui::Color32::from_rgb(0, 255, 0),
+                warning: egui::Color32::from_rgb(255, 165, 0),
+                error: egui::Color32::from_rgb(255, 0, 0),
+                monospace_bg: egui::Color32::BLACK,
+            },
+        }
+    }
+}
+
+// --- i18n Subsystem ---
+
+/// 已打包的界面语言；新增语言需要在这里加一个变体，并在 `Language::bundled_json`
+/// 里登记对应的 JSON 表，不需要改动任何 UI 代码
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum Language {
+    #[default]
+    En,
+    ZhHans,
+    ZhHant,
+    Ja,
+}
+
+impl Language {
+    fn code(&self) -> &'static str {
+        match self {
+            Language::En => "en",
+            Language::ZhHans => "zh-Hans",
+            Language::ZhHant => "zh-Hant",
+            Language::Ja => "ja",
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Language::En => "English",
+            Language::ZhHans => "简体中文",
+            Language::ZhHant => "繁體中文",
+            Language::Ja => "日本語",
+        }
+    }
+
+    const ALL: [Language; 4] = [
+        Language::En,
+        Language::ZhHans,
+        Language::ZhHant,
+        Language::Ja,
+    ];
+
+    /// 内置的兜底翻译表，在 `./src/locales/<code>.json` 缺失或解析失败时使用，
+    /// 保证界面永远有文本可显示
+    fn bundled_json(&self) -> &'static str {
+        match self {
+            Language::En => include_str!("../locales/en.json"),
+            Language::ZhHans => include_str!("../locales/zh-Hans.json"),
+            Language::ZhHant => include_str!("../locales/zh-Hant.json"),
+            Language::Ja => include_str!("../locales/ja.json"),
+        }
+    }
+}
+
+/// 单一语言的翻译表：key -> 本地化文本。先尝试从磁盘加载 `./src/locales/<code>.json`
+/// （便于用户或译者在不重新编译的情况下修正文案），解析失败或文件不存在则回退到打包的默认表
+struct Locale {
+    table: std::collections::HashMap<String, String>,
+}
+
+impl Locale {
+    fn load(language: Language) -> Self {
+        let on_disk = PathBuf::from(format!("./src/locales/{}.json", language.code()));
+        let table = fs::read_to_string(&on_disk)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_else(|| {
+                serde_json::from_str(language.bundled_json())
+                    .unwrap_or_else(|_| std::collections::HashMap::new())
+            });
+        Self { table }
+    }
+
+    /// 查表翻译；缺失的 key 原样返回，这样即便漏翻译某个字符串界面也不会出现空白
+    fn tr(&self, key: &str) -> String {
+        self.table
+            .get(key)
+            .cloned()
+            .unwrap_or_else(|| key.to_string())
+    }
+}
+
 // --- App State and Logic ---
 
 #[derive(PartialEq)]
@@ -175,6 +793,27 @@ enum AppTab {
     Settings,
     EnvironmentCheck,
     NetworkCheck,
+    ModelTest,
+}
+
+/// 从行内 ERROR/WARN/INFO 关键字或 ANSI 颜色推断出的日志严重级别，驱动日志面板的严重级别过滤
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum LogSeverity {
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogSeverity {
+    fn label(&self) -> &'static str {
+        match self {
+            LogSeverity::Info => "Info",
+            LogSeverity::Warn => "Warn",
+            LogSeverity::Error => "Error",
+        }
+    }
+
+    const ALL: [LogSeverity; 3] = [LogSeverity::Error, LogSeverity::Warn, LogSeverity::Info];
 }
 
 #[derive(Clone, Debug)]
@@ -200,6 +839,45 @@ enum CheckStatus {
     Failure,
 }
 
+/// "Check Proxy" 诊断结果——出口 IP 与往返延迟，由 `check_proxy` 并发填充
+#[derive(Clone, Debug)]
+struct ProxyCheckResult {
+    status: CheckStatus,
+    exit_ip: Option<String>,
+    latency_ms: Option<u128>,
+}
+
+impl Default for ProxyCheckResult {
+    fn default() -> Self {
+        Self {
+            status: CheckStatus::Pending,
+            exit_ip: None,
+            latency_ms: None,
+        }
+    }
+}
+
+/// "Compare Models" 面板中单个参与模型的对比结果，由 `run_model_comparison` 并发填充
+#[derive(Clone, Debug)]
+struct ModelComparisonResult {
+    model_path: String,
+    /// 与输入样本按行对应的译文；请求失败时为空
+    translations: Vec<String>,
+    latency_ms: u128,
+    token_count: Option<u64>,
+    error: Option<String>,
+}
+
+/// "Model Test" 标签页中单个 `online_config` 模型的测试结果，由 `run_model_test` 并发填充
+#[derive(Clone, Debug)]
+struct ModelTestResult {
+    model_path: String,
+    model_name: String,
+    response_text: String,
+    latency_ms: u128,
+    error: Option<String>,
+}
+
 struct MyApp {
     config: Config,
     config_path: PathBuf,
@@ -207,10 +885,12 @@ struct MyApp {
     active_tab: AppTab,
     env_check_results: Arc<Mutex<Vec<EnvironmentCheckItem>>>,
     network_check_results: Arc<Mutex<Vec<NetworkCheckResult>>>,
-    node_server: Arc<Mutex<ProcessState>>,
-    python_server: Arc<Mutex<ProcessState>>,
+    /// 每个服务一份运行时状态，与 `config.services` 按下标一一对应
+    services: Vec<Arc<Mutex<ProcessState>>>,
     log_receiver: crossbeam_channel::Receiver<String>,
     log_sender: crossbeam_channel::Sender<String>,
+    // 文件监听线程发来的"该重载/重启谁"事件，已经过防抖
+    watch_receiver: crossbeam_channel::Receiver<WatchTarget>,
     logs: Vec<String>,
     // 缓存解析好的 LayoutJob，避免每帧都重新解析 ANSI
     log_jobs: Vec<egui::text::LayoutJob>,
@@ -219,6 +899,42 @@ struct MyApp {
     // 当用户正在选择日志（鼠标按下）时，暂停自动滚动与自动焦点
     user_selecting_logs: bool,
     logs_scroll_to_bottom: bool,
+    /// 日志过滤框中的文本，为空表示不过滤
+    log_filter: String,
+    /// 是否将 `log_filter` 作为正则表达式解释，而非普通子串
+    log_filter_regex: bool,
+    /// 限定只看某一路流（如 "Node Server-stdout"），None 表示显示全部
+    log_filter_stream: Option<String>,
+    /// 按严重级别过滤日志行（从行内 ERROR/WARN/INFO 关键字或 ANSI 颜色推断），None 表示显示全部
+    log_severity_filter: Option<LogSeverity>,
+    /// 与 `logs`/`log_jobs` 按下标一一对应的可见性掩码；只有在过滤条件变化或追加新行时
+    /// 才重新计算（见 `refresh_log_visibility_mask`），避免在 500ms 节流重绘下逐帧重新过滤
+    log_visible_mask: Vec<bool>,
+    /// 上一次计算 `log_visible_mask` 时使用的过滤条件快照，用来判断过滤条件是否已变化
+    log_filter_snapshot: (String, bool, Option<String>, Option<LogSeverity>),
+    /// 当前 `config.ui_language` 对应的已加载翻译表；语言切换时重新加载
+    locale: Locale,
+    /// "Compare Models" 面板的待翻译样本文本（每行一条字幕）
+    compare_input: String,
+    /// 与 `config.models` 按下标一一对应，标记该模型是否参与本次对比
+    compare_selected: Vec<bool>,
+    /// 各参与模型的对比结果，由后台线程并发填充
+    compare_results: Arc<Mutex<Vec<ModelComparisonResult>>>,
+    /// 本轮对比派发出去的模型数；`compare_results.len() < compare_expected` 期间视为仍在运行，
+    /// 用于禁用"Run Comparison"按钮防止重复触发
+    compare_expected: usize,
+    /// "Check Proxy" 诊断的最近一次结果
+    proxy_check_result: Arc<Mutex<ProxyCheckResult>>,
+    /// 本次网络检查是否绕过 `config.proxy`（界面上的全局开关，不持久化）
+    network_check_bypass_proxy: bool,
+    /// "Model Test" 标签页的样本源文本，会替换进各模型 `translation_prompt` 里的 `{context}`
+    model_test_input: String,
+    /// 各 `online_config` 模型的测试结果，由后台线程并发填充
+    model_test_results: Arc<Mutex<Vec<ModelTestResult>>>,
+    /// 本轮测试派发出去的模型数；语义同 `compare_expected`，用于禁用按钮防止重复触发
+    model_test_expected: usize,
+    /// Settings -> Profiles 中"另存为"输入框里的待用名称
+    profile_name_input: String,
 }
 
 impl MyApp {
@@ -275,6 +991,9 @@ impl MyApp {
             }
         };
 
+        cc.egui_ctx.set_visuals(config.theme.visuals());
+        let locale = Locale::load(config.ui_language);
+
         let sites_to_check = vec![
             "https://musicbrainz.org/",
             "https://music.163.com/",
@@ -317,6 +1036,31 @@ impl MyApp {
             })
             .collect();
 
+        // 监听每个服务声明的源文件和 config.json，变化时（经防抖）驱动自动重载/重启
+        let (watch_sender, watch_receiver) = crossbeam_channel::unbounded();
+        let mut watch_paths: Vec<(PathBuf, WatchTarget)> = config
+            .services
+            .iter()
+            .filter_map(|svc| {
+                svc.watch_path
+                    .as_ref()
+                    .map(|p| (PathBuf::from(p), WatchTarget::Service(svc.id.clone())))
+            })
+            .collect();
+        watch_paths.push((config_path.clone(), WatchTarget::Config));
+        spawn_file_watcher(watch_paths, watch_sender, log_sender.clone());
+
+        let services = config
+            .services
+            .iter()
+            .map(|svc| {
+                Arc::new(Mutex::new(ProcessState {
+                    auto_restart: svc.auto_restart,
+                    ..ProcessState::default()
+                }))
+            })
+            .collect();
+
         Self {
             config,
             config_path,
@@ -324,18 +1068,40 @@ impl MyApp {
             active_tab: AppTab::Launcher,
             env_check_results: Arc::new(Mutex::new(env_check_results)),
             network_check_results: Arc::new(Mutex::new(network_check_results)),
-            node_server: Arc::new(Mutex::new(ProcessState::default())),
-            python_server: Arc::new(Mutex::new(ProcessState::default())),
+            services,
             log_receiver,
             log_sender,
+            watch_receiver,
             logs: Vec::new(),
             log_jobs: Vec::new(),
             logs_text: String::new(),
             user_selecting_logs: false,
             logs_scroll_to_bottom: false,
+            log_filter: String::new(),
+            log_filter_regex: false,
+            log_filter_stream: None,
+            log_severity_filter: None,
+            log_visible_mask: Vec::new(),
+            log_filter_snapshot: (String::new(), false, None, None),
+            locale,
+            compare_input: String::new(),
+            compare_selected: Vec::new(),
+            compare_results: Arc::new(Mutex::new(Vec::new())),
+            compare_expected: 0,
+            proxy_check_result: Arc::new(Mutex::new(ProxyCheckResult::default())),
+            network_check_bypass_proxy: false,
+            model_test_input: String::new(),
+            model_test_results: Arc::new(Mutex::new(Vec::new())),
+            model_test_expected: 0,
+            profile_name_input: String::new(),
         }
     }
 
+    /// 按当前界面语言查表翻译；缺失的 key 原样返回
+    fn tr(&self, key: &str) -> String {
+        self.locale.tr(key)
+    }
+
     fn save_config(&mut self) {
         match serde_json::to_string_pretty(&self.config) {
             Ok(json_content) => {
@@ -351,59 +1117,287 @@ impl MyApp {
         }
     }
 
-    fn run_environment_checks(&mut self, ctx: egui::Context) {
-        let results_arc = Arc::clone(&self.env_check_results);
-        {
-            let mut results = results_arc.lock().unwrap();
-            for item in results.iter_mut() {
-                item.status = CheckStatus::Checking;
-            }
-        }
-        ctx.request_repaint();
-
-        let ctx_clone = ctx.clone();
-        thread::spawn(move || {
-            let node = Self::check_command_exists("node");
-            let python = Self::check_command_exists("python");
-            let ffmpeg = Self::check_command_exists("ffmpeg");
-            let ffprobe = Self::check_command_exists("ffprobe");
-            let es_exe = Path::new("./src/everything_sdk/es.exe").exists();
-            let everything_64_dll = Path::new("./src/everything_sdk/dll/Everything64.dll").exists();
-            let everything_32_dll = Path::new("./src/everything_sdk/dll/Everything32.dll").exists();
+    /// 存放命名配置档（profile）的目录，与 `config_path`/`log_dir` 同级约定
+    fn profiles_dir() -> PathBuf {
+        PathBuf::from("./src/profiles")
+    }
 
-            let mut sys = System::new_all();
-            sys.refresh_processes();
-            let everything_process = sys.processes_by_name("Everything.exe").next().is_some();
+    /// 校验 profile 名称仅含 `[A-Za-z0-9_-]`，避免其被拼接进路径后越出 `profiles_dir()`
+    /// （例如 `../../etc/passwd` 这类路径穿越）
+    fn is_valid_profile_name(name: &str) -> bool {
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    }
 
-            // Update results
-            {
-                let mut results = results_arc.lock().unwrap();
-                for item in results.iter_mut() {
-                    let success = match item.name.as_str() {
-                        "node" => node,
-                        "python" => python,
-                        "ffmpeg" => ffmpeg,
-                        "ffprobe" => ffprobe,
-                        "Everything (process)" => everything_process,
-                        "es.exe" => es_exe,
-                        "Everything64.dll" => everything_64_dll,
-                        "Everything32.dll" => everything_32_dll,
-                        _ => false,
-                    };
-                    item.status = if success {
-                        CheckStatus::Success
-                    } else {
-                        CheckStatus::Failure
-                    };
+    /// 列出 `profiles_dir()` 下已有的配置档名称（不含 `.json` 后缀），按字母排序
+    fn list_profiles(&self) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(Self::profiles_dir()) else {
+            return Vec::new();
+        };
+        let mut names: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let path = entry.path();
+                if path.extension().and_then(|e| e.to_str()) == Some("json") {
+                    path.file_stem()
+                        .and_then(|s| s.to_str())
+                        .map(|s| s.to_string())
+                } else {
+                    None
                 }
-            }
-            ctx_clone.request_repaint();
-        });
+            })
+            .collect();
+        names.sort();
+        names
     }
 
-    fn check_command_exists(command: &str) -> bool {
-        let mut cmd = Command::new(command);
-        cmd.arg("-version");
+    /// 把当前 `config` 另存为 `profiles_dir()/{name}.json`，供稍后 `load_profile` 切换回来
+    fn save_profile_as(&mut self, name: &str) {
+        if name.trim().is_empty() {
+            self.status_message = "Profile name is empty".to_string();
+            return;
+        }
+        if !Self::is_valid_profile_name(name.trim()) {
+            self.status_message =
+                "Profile name may only contain letters, digits, '_' and '-'".to_string();
+            return;
+        }
+        let dir = Self::profiles_dir();
+        if let Err(e) = fs::create_dir_all(&dir) {
+            self.status_message = format!("Error creating profiles directory: {}", e);
+            return;
+        }
+        let path = dir.join(format!("{}.json", name.trim()));
+        match serde_json::to_string_pretty(&self.config) {
+            Ok(json_content) => match fs::write(&path, json_content) {
+                Ok(_) => {
+                    self.status_message = format!("Saved profile '{}'.", name.trim());
+                }
+                Err(e) => {
+                    self.status_message = format!("Error saving profile: {}", e);
+                }
+            },
+            Err(e) => {
+                self.status_message = format!("Error serializing profile: {}", e);
+            }
+        }
+    }
+
+    /// 从 `profiles_dir()/{name}.json` 加载并替换当前运行中的 `config`
+    /// （与 `WatchTarget::Config` 的热重载路径一致，不重建 `services` 运行时状态）
+    fn load_profile(&mut self, name: &str) {
+        if !Self::is_valid_profile_name(name) {
+            self.status_message =
+                "Profile name may only contain letters, digits, '_' and '-'".to_string();
+            return;
+        }
+        let path = Self::profiles_dir().join(format!("{}.json", name));
+        match fs::read_to_string(&path) {
+            Ok(content) => match serde_json::from_str(&content) {
+                Ok(parsed) => {
+                    self.config = parsed;
+                    self.locale = Locale::load(self.config.ui_language);
+                    self.status_message = format!("Loaded profile '{}'.", name);
+                }
+                Err(e) => {
+                    self.status_message = format!("Error parsing profile '{}': {}", name, e);
+                }
+            },
+            Err(e) => {
+                self.status_message = format!("Error reading profile '{}': {}", name, e);
+            }
+        }
+    }
+
+    /// 通过原生文件对话框把当前配置导出到任意位置，便于在机器间分享
+    fn export_profile(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Config files", &["json"])
+            .set_file_name("webplayer-profile.json")
+            .save_file()
+        else {
+            return;
+        };
+
+        match serde_json::to_string_pretty(&self.config) {
+            Ok(json_content) => match fs::write(&path, json_content) {
+                Ok(_) => {
+                    self.status_message = format!("Exported profile to {}", path.display());
+                }
+                Err(e) => {
+                    self.status_message = format!("Failed to export profile: {}", e);
+                }
+            },
+            Err(e) => {
+                self.status_message = format!("Error serializing profile: {}", e);
+            }
+        }
+    }
+
+    /// 通过原生文件对话框选取一个配置 JSON 文件并加载为当前配置
+    fn import_profile(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Config files", &["json"])
+            .pick_file()
+        else {
+            return;
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(content) => match serde_json::from_str(&content) {
+                Ok(parsed) => {
+                    self.config = parsed;
+                    self.locale = Locale::load(self.config.ui_language);
+                    self.status_message = format!("Imported profile from {}", path.display());
+                }
+                Err(e) => {
+                    self.status_message = format!("Error parsing imported profile: {}", e);
+                }
+            },
+            Err(e) => {
+                self.status_message = format!("Error reading imported profile: {}", e);
+            }
+        }
+    }
+
+    /// 从当前配置构造滚动日志参数，供每次 `spawn_process` 调用使用
+    fn log_file_config(&self) -> LogFileConfig {
+        LogFileConfig {
+            dir: PathBuf::from(&self.config.log_dir),
+            max_size_bytes: self.config.log_max_size_mb * 1024 * 1024,
+            max_backups: self.config.log_max_backups,
+        }
+    }
+
+    /// 在 `services`/`config.services` 中查找给定 id 对应的下标
+    fn service_index(&self, id: &str) -> Option<usize> {
+        self.config.services.iter().position(|s| s.id == id)
+    }
+
+    /// 若该服务启用了 `auto_reload` 且正在运行，则在监听到的变化之后重启它
+    /// （复用 Restart 按钮的停止 + 延迟拉起流程）
+    fn maybe_restart_on_watch(&self, service_id: &str) {
+        let Some(idx) = self.service_index(service_id) else {
+            return;
+        };
+        let state = Arc::clone(&self.services[idx]);
+        let service = self.config.services[idx].clone();
+        if !state.lock().unwrap().auto_reload || !MyApp::is_process_running(&state) {
+            return;
+        }
+        self.log_sender
+            .send(format!(
+                "[{}] Watched file changed, restarting.",
+                service.display_name
+            ))
+            .ok();
+        let notify_enabled = self.config.notifications_enabled;
+        let log_file_config = self.log_file_config();
+        MyApp::stop_process(
+            Arc::clone(&state),
+            &service.display_name,
+            &self.log_sender,
+            notify_enabled,
+        );
+
+        let sender = self.log_sender.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(500));
+            Self::spawn_process(service, sender, state, notify_enabled, log_file_config);
+        });
+    }
+
+    /// 轮询文件监听线程发来的事件：config.json 变化就原地重新加载，服务源文件变化则按
+    /// 各自的 `auto_reload` 开关决定是否重启
+    fn process_watch_events(&mut self) {
+        while let Ok(target) = self.watch_receiver.try_recv() {
+            match target {
+                WatchTarget::Config => match fs::read_to_string(&self.config_path) {
+                    Ok(content) => match serde_json::from_str(&content) {
+                        Ok(parsed) => {
+                            self.config = parsed;
+                            self.log_sender
+                                .send("[watch] config.json changed, reloaded.".to_string())
+                                .ok();
+                        }
+                        Err(e) => {
+                            self.log_sender
+                                .send(format!(
+                                    "[watch] config.json changed but failed to parse: {}",
+                                    e
+                                ))
+                                .ok();
+                        }
+                    },
+                    Err(e) => {
+                        self.log_sender
+                            .send(format!("[watch] Failed to read changed config.json: {}", e))
+                            .ok();
+                    }
+                },
+                WatchTarget::Service(id) => self.maybe_restart_on_watch(&id),
+            }
+        }
+    }
+
+    // 注：本函数只做本地命令/文件/进程存在性检查，不发起任何网络请求，因此没有
+    // agent 可以挂代理——`config.proxy` 只接入实际发起外部 HTTP 请求的
+    // `run_network_checks_parallel` / `check_proxy` / `run_model_test`。
+    fn run_environment_checks(&mut self, ctx: egui::Context) {
+        let results_arc = Arc::clone(&self.env_check_results);
+        {
+            let mut results = results_arc.lock().unwrap();
+            for item in results.iter_mut() {
+                item.status = CheckStatus::Checking;
+            }
+        }
+        ctx.request_repaint();
+
+        let ctx_clone = ctx.clone();
+        thread::spawn(move || {
+            let node = Self::check_command_exists("node");
+            let python = Self::check_command_exists("python");
+            let ffmpeg = Self::check_command_exists("ffmpeg");
+            let ffprobe = Self::check_command_exists("ffprobe");
+            let es_exe = Path::new("./src/everything_sdk/es.exe").exists();
+            let everything_64_dll = Path::new("./src/everything_sdk/dll/Everything64.dll").exists();
+            let everything_32_dll = Path::new("./src/everything_sdk/dll/Everything32.dll").exists();
+
+            let mut sys = System::new_all();
+            sys.refresh_processes();
+            let everything_process = sys.processes_by_name("Everything.exe").next().is_some();
+
+            // Update results
+            {
+                let mut results = results_arc.lock().unwrap();
+                for item in results.iter_mut() {
+                    let success = match item.name.as_str() {
+                        "node" => node,
+                        "python" => python,
+                        "ffmpeg" => ffmpeg,
+                        "ffprobe" => ffprobe,
+                        "Everything (process)" => everything_process,
+                        "es.exe" => es_exe,
+                        "Everything64.dll" => everything_64_dll,
+                        "Everything32.dll" => everything_32_dll,
+                        _ => false,
+                    };
+                    item.status = if success {
+                        CheckStatus::Success
+                    } else {
+                        CheckStatus::Failure
+                    };
+                }
+            }
+            ctx_clone.request_repaint();
+        });
+    }
+
+    fn check_command_exists(command: &str) -> bool {
+        let mut cmd = Command::new(command);
+        cmd.arg("-version");
 
         #[cfg(target_os = "windows")]
         {
@@ -418,31 +1412,147 @@ impl MyApp {
 
 // --- Process Management ---
 impl MyApp {
-    fn spawn_process(
-        command: &str,
-        args: &[&str],
-        working_dir: Option<&str>,
-        log_sender: crossbeam_channel::Sender<String>,
-        process_state: Arc<Mutex<ProcessState>>,
-        process_name: &'static str,
+    /// 把服务里配置的相对路径（工作目录、依赖清单……）解析到启动器可执行文件所在目录下；
+    /// 已经是绝对路径的原样返回
+    fn resolve_service_path(dir: &str) -> PathBuf {
+        if Path::new(dir).is_absolute() {
+            return PathBuf::from(dir);
+        }
+        if let Ok(exe_path) = std::env::current_exe() {
+            if let Some(exe_dir) = exe_path.parent() {
+                return exe_dir.join(dir);
+            }
+        }
+        PathBuf::from(dir)
+    }
+
+    /// 在 (重新) 启动服务前做一次依赖同步：对 `dependency_manifest` 内容做哈希，
+    /// 与上次记录的哈希对比，不一致（含首次运行）就执行 `dependency_install_cmd`，
+    /// 并把其 stdout/stderr 原样转发进日志管道；哈希缓存落在 `log_dir/<id>.deps.hash`
+    fn sync_dependencies(
+        service: &ServiceConfig,
+        resolved_working_dir: Option<&Path>,
+        log_file_config: &LogFileConfig,
+        log_sender: &crossbeam_channel::Sender<String>,
     ) {
-        let mut cmd = Command::new(command);
-        cmd.args(args);
-        if let Some(dir) = working_dir {
-            // If a relative path is provided, resolve it against the launcher's executable directory
-            let resolved_dir = if Path::new(dir).is_absolute() {
-                PathBuf::from(dir)
-            } else {
-                if let Ok(exe_path) = std::env::current_exe() {
-                    if let Some(exe_dir) = exe_path.parent() {
-                        exe_dir.join(dir)
-                    } else {
-                        PathBuf::from(dir)
+        let (Some(manifest), Some(install_cmd)) =
+            (&service.dependency_manifest, &service.dependency_install_cmd)
+        else {
+            return;
+        };
+        if install_cmd.is_empty() {
+            return;
+        }
+
+        let manifest_path = match resolved_working_dir {
+            Some(dir) => dir.join(manifest),
+            None => PathBuf::from(manifest),
+        };
+        let content = match fs::read(&manifest_path) {
+            Ok(content) => content,
+            Err(e) => {
+                log_sender
+                    .send(format!(
+                        "[{}] Dependency manifest '{}' unreadable, skipping sync: {}",
+                        service.display_name,
+                        manifest_path.display(),
+                        e
+                    ))
+                    .ok();
+                return;
+            }
+        };
+
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        std::hash::Hash::hash(&content, &mut hasher);
+        let new_hash = std::hash::Hasher::finish(&hasher).to_string();
+
+        let hash_cache_path = log_file_config.dir.join(format!("{}.deps.hash", service.id));
+        let previous_hash = fs::read_to_string(&hash_cache_path).ok();
+        if previous_hash.as_deref() == Some(new_hash.as_str()) {
+            return;
+        }
+
+        log_sender
+            .send(format!(
+                "[{}] Dependency manifest changed, running: {}",
+                service.display_name,
+                install_cmd.join(" ")
+            ))
+            .ok();
+
+        let mut install = Command::new(&install_cmd[0]);
+        install.args(&install_cmd[1..]);
+        if let Some(dir) = resolved_working_dir {
+            install.current_dir(dir);
+        }
+        #[cfg(target_os = "windows")]
+        {
+            use std::os::windows::process::CommandExt;
+            const CREATE_NO_WINDOW: u32 = 0x08000000;
+            install.creation_flags(CREATE_NO_WINDOW);
+        }
+
+        match install.output() {
+            Ok(output) => {
+                for line in String::from_utf8_lossy(&output.stdout).lines() {
+                    log_sender
+                        .send(format!("[{}-deps] {}", service.display_name, line))
+                        .ok();
+                }
+                for line in String::from_utf8_lossy(&output.stderr).lines() {
+                    log_sender
+                        .send(format!("[{}-deps] {}", service.display_name, line))
+                        .ok();
+                }
+                if output.status.success() {
+                    if let Some(parent) = hash_cache_path.parent() {
+                        fs::create_dir_all(parent).ok();
                     }
+                    fs::write(&hash_cache_path, &new_hash).ok();
                 } else {
-                    PathBuf::from(dir)
+                    log_sender
+                        .send(format!(
+                            "[{}] Dependency install exited with {}; will retry next start.",
+                            service.display_name, output.status
+                        ))
+                        .ok();
                 }
-            };
+            }
+            Err(e) => {
+                log_sender
+                    .send(format!(
+                        "[{}] Failed to run dependency install command: {}",
+                        service.display_name, e
+                    ))
+                    .ok();
+            }
+        }
+    }
+
+    fn spawn_process(
+        service: ServiceConfig,
+        log_sender: crossbeam_channel::Sender<String>,
+        process_state: Arc<Mutex<ProcessState>>,
+        notify_enabled: bool,
+        log_file_config: LogFileConfig,
+    ) {
+        let process_name = service.display_name.clone();
+        let readiness_target = (service.readiness_host.clone(), service.readiness_port);
+        let resolved_working_dir = service.working_dir.as_deref().map(Self::resolve_service_path);
+
+        // 依赖同步：清单文件内容哈希有变化才触发安装命令，避免每次启动都重新 pip/npm install
+        Self::sync_dependencies(
+            &service,
+            resolved_working_dir.as_deref(),
+            &log_file_config,
+            &log_sender,
+        );
+
+        let mut cmd = Command::new(&service.program);
+        cmd.args(&service.args);
+        cmd.envs(&service.env);
+        if let Some(resolved_dir) = &resolved_working_dir {
             if let Some(resolved_str) = resolved_dir.to_str() {
                 cmd.current_dir(resolved_str);
             }
@@ -454,11 +1564,47 @@ impl MyApp {
         {
             use std::os::windows::process::CommandExt;
             const CREATE_NO_WINDOW: u32 = 0x08000000;
-            cmd.creation_flags(CREATE_NO_WINDOW);
+            // 独立的进程组，使得之后可以针对整个子树发送信号/绑定 Job Object
+            const CREATE_NEW_PROCESS_GROUP: u32 = 0x00000200;
+            cmd.creation_flags(CREATE_NO_WINDOW | CREATE_NEW_PROCESS_GROUP);
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            unsafe {
+                cmd.pre_exec(|| {
+                    // 让子进程成为新会话和新进程组的组长，便于之后用负 PID 对整组发送信号
+                    libc::setsid();
+                    Ok(())
+                });
+            }
         }
 
         let log_sender_clone = log_sender.clone();
         let mut state = process_state.lock().unwrap();
+
+        if let Some(existing) = state.child.as_mut() {
+            match existing.try_wait() {
+                Ok(None) => {
+                    // 仍然存活：多半是手动 Start 和自动重启在锁外撞到了一起，
+                    // 复用已经在跑的进程，不再拉起第二个子进程把第一个晾在那儿
+                    log_sender
+                        .send(format!(
+                            "[{}] Already running (PID {}), skipping duplicate start.",
+                            process_name,
+                            existing.id()
+                        ))
+                        .ok();
+                    return;
+                }
+                _ => {
+                    // 已经退出或状态查询失败，这条记录没用了，正常走下面的拉起流程
+                    state.child = None;
+                }
+            }
+        }
+
         match cmd.spawn() {
             Ok(mut child) => {
                 log_sender
@@ -468,46 +1614,102 @@ impl MyApp {
                         child.id()
                     ))
                     .ok();
+                notify_service_event(
+                    notify_enabled,
+                    &format!("{} started", process_name),
+                    &format!("PID {}", child.id()),
+                );
+
+                #[cfg(target_os = "windows")]
+                {
+                    state.job_handle = assign_to_new_job_object(&child);
+                }
 
                 let stdout = child.stdout.take().expect("Failed to open stdout");
                 let stderr = child.stderr.take().expect("Failed to open stderr");
 
                 state.child = Some(child);
-                state.status = "Running".to_string();
+                state.status = "Starting".to_string();
+                state.started_at = Some(Instant::now());
+                state.shutdown_requested = false;
+
+                // 每个服务一个滚动日志文件，stdout/stderr 两个读取线程共享同一个句柄
+                let log_file = match RotatingLogFile::open(&log_file_config, &service.id) {
+                    Ok(f) => Some(Arc::new(Mutex::new(f))),
+                    Err(e) => {
+                        log_sender
+                            .send(format!("[{}] Failed to open log file: {}", process_name, e))
+                            .ok();
+                        None
+                    }
+                };
+                state.log_file = log_file.clone();
 
                 // spawn stdout reader
                 let sender_stdout = log_sender.clone();
+                let log_file_stdout = log_file.clone();
+                let name_stdout = process_name.clone();
                 thread::spawn(move || {
                     let reader = BufReader::new(stdout);
                     for line in reader.lines() {
                         if let Ok(line) = line {
-                            sender_stdout
-                                .send(format!("[{}-stdout] {}", process_name, line))
-                                .ok();
+                            let formatted = format!("[{}-stdout] {}", name_stdout, line);
+                            if let Some(lf) = &log_file_stdout {
+                                lf.lock().unwrap().write_line(&formatted);
+                            }
+                            sender_stdout.send(formatted).ok();
                         }
                     }
                 });
 
                 // spawn stderr reader
                 let sender_stderr = log_sender.clone();
+                let log_file_stderr = log_file.clone();
+                let name_stderr = process_name.clone();
                 thread::spawn(move || {
                     let reader = BufReader::new(stderr);
                     for line in reader.lines() {
                         if let Ok(line) = line {
-                            sender_stderr
-                                .send(format!("[{}-stderr] {}", process_name, line))
-                                .ok();
+                            let formatted = format!("[{}-stderr] {}", name_stderr, line);
+                            if let Some(lf) = &log_file_stderr {
+                                lf.lock().unwrap().write_line(&formatted);
+                            }
+                            sender_stderr.send(formatted).ok();
                         }
                     }
                 });
 
-                // Spawn a monitor thread to detect if the child exits immediately (e.g., port conflict)
+                // Spawn a readiness-probe thread: poll the service's TCP port until it accepts
+                // connections (flips status Starting -> Ready / Unreachable), then keep probing
+                // at a lower cadence so a hung-but-alive process gets killed and handed back to
+                // the crash-supervisor monitor thread below.
+                {
+                    let probe_state = Arc::clone(&process_state);
+                    let probe_sender = log_sender.clone();
+                    let (probe_host, probe_port) = readiness_target.clone();
+                    let probe_name = process_name.clone();
+                    thread::spawn(move || {
+                        Self::probe_readiness(
+                            probe_state,
+                            probe_host,
+                            probe_port,
+                            probe_sender,
+                            probe_name,
+                            notify_enabled,
+                        );
+                    });
+                }
+
+                // Spawn a monitor thread to detect if the child exits, and (if enabled)
+                // drive the auto-restart supervisor with exponential backoff
                 let monitor_state = Arc::clone(&process_state);
                 let monitor_sender = log_sender_clone.clone();
-                let monitor_name = process_name;
+                let monitor_name = process_name.clone();
+                let monitor_service = service.clone();
+                let monitor_log_file_config = log_file_config.clone();
                 thread::spawn(move || {
                     loop {
-                        std::thread::sleep(std::time::Duration::from_millis(300));
+                        thread::sleep(Duration::from_millis(300));
                         let mut st = monitor_state.lock().unwrap();
                         if let Some(child_ref) = st.child.as_mut() {
                             match child_ref.try_wait() {
@@ -515,8 +1717,88 @@ impl MyApp {
                                     monitor_sender
                                         .send(format!("[{}] Process exited: {}", monitor_name, exit_status))
                                         .ok();
+                                    notify_service_event(
+                                        notify_enabled,
+                                        &format!("{} crashed", monitor_name),
+                                        &format!("Exited with {}", exit_status),
+                                    );
+
+                                    // 稳定运行过一段时间再退出的，视为新的故障周期，重置退避
+                                    let ran_long_enough = st
+                                        .started_at
+                                        .map(|t| t.elapsed() >= STABLE_UPTIME_THRESHOLD)
+                                        .unwrap_or(false);
+                                    if ran_long_enough {
+                                        st.restart_count = 0;
+                                        st.next_backoff = INITIAL_RESTART_BACKOFF;
+                                    }
+
                                     st.status = format!("Exited: {}", exit_status);
                                     st.child = None;
+                                    st.started_at = None;
+
+                                    // `stop_process` 已经把 child 置空，这里读到的理应是 false；
+                                    // 保留判断只是为了在日志里明确区分"用户主动停止"与"意外崩溃"
+                                    if st.shutdown_requested {
+                                        monitor_sender
+                                            .send(format!("[{}] Exited after a requested stop, not restarting.", monitor_name))
+                                            .ok();
+                                    } else if st.auto_restart {
+                                        if st.restart_count >= MAX_RESTART_ATTEMPTS {
+                                            st.status = format!(
+                                                "Failed permanently after {} restarts",
+                                                st.restart_count
+                                            );
+                                            monitor_sender
+                                                .send(format!(
+                                                    "[{}] Giving up after {} restart attempts.",
+                                                    monitor_name, st.restart_count
+                                                ))
+                                                .ok();
+                                            notify_service_event(
+                                                notify_enabled,
+                                                &format!("{} restart budget exceeded", monitor_name),
+                                                &format!(
+                                                    "Gave up after {} restart attempts",
+                                                    st.restart_count
+                                                ),
+                                            );
+                                        } else {
+                                            let backoff = st.next_backoff;
+                                            let attempt = st.restart_count + 1;
+                                            st.restart_count = attempt;
+                                            st.next_backoff =
+                                                (st.next_backoff * 2).min(MAX_RESTART_BACKOFF);
+                                            drop(st);
+
+                                            monitor_sender
+                                                .send(format!(
+                                                    "[{}] Auto-restarting in {:?} (attempt {}).",
+                                                    monitor_name, backoff, attempt
+                                                ))
+                                                .ok();
+                                            thread::sleep(backoff);
+
+                                            // 等待期间用户可能已经点了 Stop；重新加锁确认一遍，
+                                            // 否则会把刚设置好的 shutdown_requested 悄悄覆盖掉
+                                            if monitor_state.lock().unwrap().shutdown_requested {
+                                                monitor_sender
+                                                    .send(format!(
+                                                        "[{}] Stop requested during backoff, aborting restart.",
+                                                        monitor_name
+                                                    ))
+                                                    .ok();
+                                            } else {
+                                                MyApp::spawn_process(
+                                                    monitor_service.clone(),
+                                                    monitor_sender.clone(),
+                                                    Arc::clone(&monitor_state),
+                                                    notify_enabled,
+                                                    monitor_log_file_config.clone(),
+                                                );
+                                            }
+                                        }
+                                    }
                                     break;
                                 }
                                 Ok(None) => {
@@ -543,93 +1825,252 @@ impl MyApp {
         }
     }
 
-    fn is_process_running(process_state: &Arc<Mutex<ProcessState>>) -> bool {
-        let mut st = process_state.lock().unwrap();
-        if let Some(child) = st.child.as_mut() {
-            match child.try_wait() {
-                Ok(Some(status)) => {
-                    // process has exited — cleanup
-                    st.status = format!("Exited: {}", status);
-                    st.child = None;
-                    return false;
-                }
-                Ok(None) => return true,
-                Err(_) => return true,
-            }
-        }
-        false
-    }
-
-    fn stop_process(
+    /// 先反复 TCP 探测直到服务接受连接（或用尽重试预算），把 `ProcessState::status` 从
+    /// "Starting" 翻到 "Ready"/"Unreachable"；就绪后转入低频的常态健康检查，连续失败
+    /// `HEALTH_PROBE_FAILURE_THRESHOLD` 次就视为假死，杀掉子进程交还给崩溃监控线程处理重启
+    fn probe_readiness(
         process_state: Arc<Mutex<ProcessState>>,
-        name: &str,
-        log_sender: &crossbeam_channel::Sender<String>,
+        host: String,
+        port: u16,
+        log_sender: crossbeam_channel::Sender<String>,
+        process_name: String,
+        notify_enabled: bool,
     ) {
-        let mut state = process_state.lock().unwrap();
-        if let Some(mut child) = state.child.take() {
-            let pid = child.id();
-            let mut stopped_normally = false;
-            match child.kill() {
-                Ok(_) => {
-                    // give it a moment and wait
-                    let _ = child.wait();
-                    stopped_normally = true;
-                }
-                Err(e) => {
-                    log_sender
-                        .send(format!("[{}] child.kill() error: {}", name, e))
-                        .ok();
-                }
+        let mut ready = false;
+        for _ in 0..READINESS_MAX_ATTEMPTS {
+            if process_state.lock().unwrap().child.is_none() {
+                return; // stopped/exited before becoming ready
             }
+            if tcp_probe(&host, port, READINESS_PROBE_TIMEOUT) {
+                ready = true;
+                break;
+            }
+            thread::sleep(READINESS_PROBE_INTERVAL);
+        }
 
-            if stopped_normally {
-                log_sender.send(format!("[{}] Process stopped.", name)).ok();
-                state.status = "Stopped".to_string();
+        {
+            let mut st = process_state.lock().unwrap();
+            if st.child.is_none() {
+                return;
+            }
+            st.status = if ready { "Ready" } else { "Unreachable" }.to_string();
+        }
+
+        if ready {
+            log_sender
+                .send(format!("[{}] Service is ready on {}:{}.", process_name, host, port))
+                .ok();
+            notify_service_event(
+                notify_enabled,
+                &format!("{} ready", process_name),
+                &format!("{}:{}", host, port),
+            );
+        } else {
+            log_sender
+                .send(format!(
+                    "[{}] Port {}:{} unreachable after {} attempts.",
+                    process_name, host, port, READINESS_MAX_ATTEMPTS
+                ))
+                .ok();
+            notify_service_event(
+                notify_enabled,
+                &format!("{} unreachable", process_name),
+                &format!("{}:{} never accepted a connection", host, port),
+            );
+            return;
+        }
+
+        // 常态健康检查：周期性复探，发现"假死"则杀掉子进程，让崩溃监控线程按既有退避策略重启
+        let mut consecutive_failures = 0u32;
+        loop {
+            thread::sleep(HEALTH_PROBE_INTERVAL);
+            let pid = match process_state.lock().unwrap().child.as_ref().map(|c| c.id()) {
+                Some(pid) => pid,
+                None => return, // stopped/exited; monitor thread already handled it
+            };
+
+            if tcp_probe(&host, port, READINESS_PROBE_TIMEOUT) {
+                consecutive_failures = 0;
+                continue;
+            }
+
+            consecutive_failures += 1;
+            log_sender
+                .send(format!(
+                    "[{}] Health probe failed ({}/{}).",
+                    process_name, consecutive_failures, HEALTH_PROBE_FAILURE_THRESHOLD
+                ))
+                .ok();
+            if consecutive_failures < HEALTH_PROBE_FAILURE_THRESHOLD {
+                continue;
+            }
+
+            {
+                let mut st = process_state.lock().unwrap();
+                if st.child.is_none() {
+                    return;
+                }
+                st.status = "Unreachable".to_string();
+            }
+            log_sender
+                .send(format!(
+                    "[{}] Unresponsive on {}:{}, killing it for the crash-supervisor to restart.",
+                    process_name, host, port
+                ))
+                .ok();
+            #[cfg(unix)]
+            {
+                signal_process_group(pid as i32, libc::SIGKILL);
+            }
+            #[cfg(target_os = "windows")]
+            {
+                Command::new("taskkill")
+                    .args(["/PID", &pid.to_string(), "/T", "/F"])
+                    .output()
+                    .ok();
+            }
+            return; // 让 spawn_process 里的 monitor 线程在下一轮 try_wait 中检测退出并接管
+        }
+    }
+
+    fn is_process_running(process_state: &Arc<Mutex<ProcessState>>) -> bool {
+        let mut st = process_state.lock().unwrap();
+        if let Some(child) = st.child.as_mut() {
+            match child.try_wait() {
+                Ok(Some(status)) => {
+                    // process has exited — cleanup
+                    st.status = format!("Exited: {}", status);
+                    st.child = None;
+                    return false;
+                }
+                Ok(None) => return true,
+                Err(_) => return true,
+            }
+        }
+        false
+    }
+
+    /// 分阶段优雅停止：Unix 上先向整个进程组发送 SIGTERM，等待宽限期让其自行退出
+    /// （有机会落盘日志、关闭连接），只有超时仍存活才强制杀死整棵子进程树；
+    /// Windows 上不发送终止信号，只等完宽限期，超时后关闭 Job Object（或用 taskkill /T /F）强制结束整棵树
+    fn stop_process(
+        process_state: Arc<Mutex<ProcessState>>,
+        name: &str,
+        log_sender: &crossbeam_channel::Sender<String>,
+        notify_enabled: bool,
+    ) {
+        let mut state = process_state.lock().unwrap();
+        state.shutdown_requested = true;
+        let taken_child = state.child.take();
+        #[cfg(target_os = "windows")]
+        let job_handle = state.job_handle.take();
+        // 在可能阻塞数秒的宽限期等待之前释放锁，否则调用方（UI 线程）会在等待期间冻结
+        drop(state);
+
+        if let Some(mut child) = taken_child {
+            let pid = child.id();
+
+            #[cfg(unix)]
+            {
+                signal_process_group(pid as i32, libc::SIGTERM);
+            }
+
+            let mut exited = false;
+            let deadline = Instant::now() + GRACEFUL_SHUTDOWN_GRACE;
+            while Instant::now() < deadline {
+                match child.try_wait() {
+                    Ok(Some(_)) => {
+                        exited = true;
+                        break;
+                    }
+                    Ok(None) => thread::sleep(Duration::from_millis(100)),
+                    Err(_) => break,
+                }
+            }
+
+            let final_status = if exited {
+                log_sender
+                    .send(format!("[{}] Process stopped gracefully.", name))
+                    .ok();
+                "Stopped".to_string()
             } else {
-                // Fallback: try to force-kill the process tree on Windows using taskkill
+                log_sender
+                    .send(format!(
+                        "[{}] Did not exit within {:?}, force-killing process tree.",
+                        name, GRACEFUL_SHUTDOWN_GRACE
+                    ))
+                    .ok();
+
+                #[cfg(unix)]
+                {
+                    signal_process_group(pid as i32, libc::SIGKILL);
+                    let _ = child.wait();
+                    log_sender
+                        .send(format!("[{}] Process group killed (PID {}).", name, pid))
+                        .ok();
+                    "Stopped (killed)".to_string()
+                }
+
                 #[cfg(target_os = "windows")]
                 {
-                    let pid_str = pid.to_string();
-                    match Command::new("taskkill").args(["/PID", &pid_str, "/T", "/F"]).output() {
-                        Ok(output) => {
-                            if output.status.success() {
-                                log_sender
-                                    .send(format!("[{}] taskkill succeeded for PID {}.", name, pid))
-                                    .ok();
-                                state.status = "Stopped (taskkill)".to_string();
-                            } else {
+                    // 关闭 Job Object 句柄会因 JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE 终止整个子树
+                    if let Some(job) = job_handle {
+                        drop(job);
+                        let _ = child.wait();
+                        log_sender
+                            .send(format!("[{}] Job object terminated process tree (PID {}).", name, pid))
+                            .ok();
+                        "Stopped (killed)".to_string()
+                    } else {
+                        // 没有拿到 Job Object（例如分配失败），回退到 taskkill /T /F
+                        let pid_str = pid.to_string();
+                        match Command::new("taskkill").args(["/PID", &pid_str, "/T", "/F"]).output() {
+                            Ok(output) => {
+                                if output.status.success() {
+                                    log_sender
+                                        .send(format!("[{}] taskkill succeeded for PID {}.", name, pid))
+                                        .ok();
+                                    "Stopped (taskkill)".to_string()
+                                } else {
+                                    log_sender
+                                        .send(format!(
+                                            "[{}] taskkill failed for PID {}. stderr: {}",
+                                            name,
+                                            pid,
+                                            String::from_utf8_lossy(&output.stderr)
+                                        ))
+                                        .ok();
+                                    format!("Failed to stop: taskkill failed (PID {})", pid)
+                                }
+                            }
+                            Err(e) => {
                                 log_sender
-                                    .send(format!(
-                                        "[{}] taskkill failed for PID {}. stderr: {}",
-                                        name,
-                                        pid,
-                                        String::from_utf8_lossy(&output.stderr)
-                                    ))
+                                    .send(format!("[{}] Failed to run taskkill: {}", name, e))
                                     .ok();
-                                state.status = format!("Failed to stop: taskkill failed (PID {})", pid);
+                                format!("Failed to stop: {}", e)
                             }
                         }
-                        Err(e) => {
-                            log_sender
-                                .send(format!("[{}] Failed to run taskkill: {}", name, e))
-                                .ok();
-                            state.status = format!("Failed to stop: {}", e);
-                        }
                     }
                 }
+            };
 
-                #[cfg(not(target_os = "windows"))]
-                {
-                    log_sender
-                        .send(format!("[{}] Unable to guarantee stop; child.kill() failed.", name))
-                        .ok();
-                    state.status = "Failed to stop process".to_string();
-                }
-            }
+            // 重新获取锁，写回最终状态；用户手动停止视为新的生命周期，清除遗留的重启计数和退避
+            let mut state = process_state.lock().unwrap();
+            state.status = final_status;
+            state.restart_count = 0;
+            state.next_backoff = INITIAL_RESTART_BACKOFF;
+            state.started_at = None;
+            drop(state);
+            notify_service_event(
+                notify_enabled,
+                &format!("{} stopped", name),
+                "Stopped by user.",
+            );
         } else {
             log_sender
                 .send(format!("[{}] Process not running.", name))
                 .ok();
+            let mut state = process_state.lock().unwrap();
             state.status = "Not running".to_string();
         }
     }
@@ -642,184 +2083,453 @@ impl MyApp {
         ui.add_space(10.0);
 
         // --- Controls ---
+        // 每个服务一行：Start/Stop/Restart 三个按钮 + Auto-restart/Auto-reload 开关，
+        // 完全由 `config.services` 驱动，增删一个服务条目即可增删一行
         egui::Grid::new("launcher_controls")
-            .num_columns(4)
+            .num_columns(5)
             .show(ui, |ui| {
-                // Node Server
-                ui.label("Node Server:");
-                if ui.button("Start").clicked() {
-                    if MyApp::is_process_running(&self.node_server) {
-                        self.log_sender
-                            .send("[Node] Already running, start skipped.".to_string())
-                            .ok();
-                    } else {
+                for i in 0..self.config.services.len() {
+                    let service = self.config.services[i].clone();
+                    let state = Arc::clone(&self.services[i]);
+
+                    ui.label(format!("{}:", service.display_name));
+                    if ui.button("Start").clicked() {
+                        if MyApp::is_process_running(&state) {
+                            self.log_sender
+                                .send(format!(
+                                    "[{}] Already running, start skipped.",
+                                    service.display_name
+                                ))
+                                .ok();
+                        } else {
+                            let sender = self.log_sender.clone();
+                            let state = Arc::clone(&state);
+                            let notify_enabled = self.config.notifications_enabled;
+                            let log_file_config = self.log_file_config();
+                            let service = service.clone();
+                            thread::spawn(move || {
+                                Self::spawn_process(
+                                    service,
+                                    sender,
+                                    state,
+                                    notify_enabled,
+                                    log_file_config,
+                                );
+                            });
+                        }
+                    }
+                    if ui.button("Stop").clicked() {
+                        // 优雅停止可能要等满 GRACEFUL_SHUTDOWN_GRACE 才强杀，放到后台线程
+                        // 执行，避免等待期间冻结 egui 主线程（UI 无法重绘/响应输入）
                         let sender = self.log_sender.clone();
-                        let state = Arc::clone(&self.node_server);
+                        let state = Arc::clone(&state);
+                        let notify_enabled = self.config.notifications_enabled;
+                        let display_name = service.display_name.clone();
                         thread::spawn(move || {
-                            Self::spawn_process("node", &["server.js"], Some("./src"), sender, state, "Node");
+                            MyApp::stop_process(state, &display_name, &sender, notify_enabled);
                         });
                     }
-                }
-                if ui.button("Stop").clicked() {
-                    MyApp::stop_process(Arc::clone(&self.node_server), "Node", &self.log_sender);
-                }
-                if ui.button("Restart").clicked() {
-                    MyApp::stop_process(Arc::clone(&self.node_server), "Node", &self.log_sender);
-                    let sender = self.log_sender.clone();
-                    let state = Arc::clone(&self.node_server);
-                    thread::spawn(move || {
-                        thread::sleep(std::time::Duration::from_millis(500));
-                        Self::spawn_process("node", &["server.js"], Some("./src"), sender, state, "Node");
-                    });
-                }
-                ui.end_row();
-
-                // Python Backend
-                ui.label("Python Backend:");
-                if ui.button("Start").clicked() {
-                    if MyApp::is_process_running(&self.python_server) {
-                        self.log_sender
-                            .send("[Python] Already running, start skipped.".to_string())
-                            .ok();
-                    } else {
+                    if ui.button("Restart").clicked() {
+                        // 重启前滚动到新的会话日志文件，而不是让新实例的输出追加进旧会话
+                        if let Some(lf) = state.lock().unwrap().log_file.clone() {
+                            lf.lock().unwrap().start_new_session();
+                        }
                         let sender = self.log_sender.clone();
-                        let state = Arc::clone(&self.python_server);
+                        let state = Arc::clone(&state);
+                        let notify_enabled = self.config.notifications_enabled;
+                        let log_file_config = self.log_file_config();
+                        let service = service.clone();
                         thread::spawn(move || {
+                            MyApp::stop_process(
+                                Arc::clone(&state),
+                                &service.display_name,
+                                &sender,
+                                notify_enabled,
+                            );
+                            thread::sleep(Duration::from_millis(500));
                             Self::spawn_process(
-                                "python",
-                                &["subtitle_process_backend.py"],
-                                Some("./src"),
+                                service,
                                 sender,
                                 state,
-                                "Python",
+                                notify_enabled,
+                                log_file_config,
                             );
                         });
                     }
+                    {
+                        let mut st = state.lock().unwrap();
+                        ui.checkbox(&mut st.auto_restart, "Auto-restart");
+                        ui.checkbox(&mut st.auto_reload, "Auto-reload");
+                        ui.label(format!("Restarts: {}", st.restart_count));
+                    }
+                    ui.end_row();
                 }
-                if ui.button("Stop").clicked() {
-                    MyApp::stop_process(Arc::clone(&self.python_server), "Python", &self.log_sender);
-                }
-                if ui.button("Restart").clicked() {
-                    MyApp::stop_process(Arc::clone(&self.python_server), "Python", &self.log_sender);
-                    let sender = self.log_sender.clone();
-                    let state = Arc::clone(&self.python_server);
-                    thread::spawn(move || {
-                        thread::sleep(std::time::Duration::from_millis(500));
-                        Self::spawn_process(
-                            "python",
-                            &["subtitle_process_backend.py"],
-                            Some("./src"),
-                            sender,
-                            state,
-                            "Python",
-                        );
-                    });
-                }
-                ui.end_row();
             });
 
+        ui.add_space(5.0);
+        ui.checkbox(
+            &mut self.config.notifications_enabled,
+            "Desktop notifications on service state changes",
+        );
+
         // --- Status ---
         ui.add_space(10.0);
         ui.separator();
-        ui.heading("Status");
-        let node_status = self.node_server.lock().unwrap().status.clone();
-        let python_status = self.python_server.lock().unwrap().status.clone();
-        ui.label(format!("Node Server: {}", node_status));
-        ui.label(format!("Python Backend: {}", python_status));
+        ui.heading(self.tr("status.heading"));
+        for (service, state) in self.config.services.iter().zip(self.services.iter()) {
+            let status = state.lock().unwrap().status.clone();
+            ui.label(format!("{}: {}", service.display_name, status));
+        }
 
         // --- Logs ---
         ui.add_space(10.0);
         ui.separator();
-        ui.heading("Logs");
+        ui.heading(self.tr("logs.heading"));
         // 使用可编辑但逻辑上只读的 TextEdit 来支持长选中复制
         ui.horizontal(|ui| {
             // Clear button
-            if ui.button("Clear Logs").clicked() {
+            if ui.button(self.tr("logs.clear")).clicked() {
+                // 滚动到新会话文件而非静默丢弃：旧日志仍保留在磁盘上可供事后查阅
+                for state in &self.services {
+                    if let Some(lf) = state.lock().unwrap().log_file.clone() {
+                        lf.lock().unwrap().start_new_session();
+                    }
+                }
                 // 清空本地缓存
                 self.logs.clear();
                 self.log_jobs.clear();
+                self.log_visible_mask.clear();
                 self.logs_text.clear();
                 self.logs_scroll_to_bottom = false;
                 // 尝试清空接收队列（非阻塞）
                 while let Ok(_) = self.log_receiver.try_recv() {
                     // discard
                 }
-                self.status_message = "Logs cleared".to_string();
+                self.status_message = "Logs cleared (previous session archived to disk)".to_string();
             }
 
-            // 显示当前日志条数
-            ui.label(format!("Lines: {}", self.logs.len()));
+            if ui.button(self.tr("logs.export")).clicked() {
+                self.export_logs();
+            }
+
+            // 显示当前日志条数（过滤时显示匹配数/总数）
+            self.refresh_log_visibility_mask();
+            if self.log_filter.is_empty()
+                && self.log_filter_stream.is_none()
+                && self.log_severity_filter.is_none()
+            {
+                ui.label(format!("Lines: {}", self.logs.len()));
+            } else {
+                let matched = self.log_visible_mask.iter().filter(|v| **v).count();
+                ui.label(format!("Lines: {} / {}", matched, self.logs.len()));
+            }
         });
 
-        egui::ScrollArea::vertical()
-            .max_height(300.0)
-            .auto_shrink([false, false])
-            .show(ui, |ui| {
-                ui.set_max_width(ui.available_width());
+        // 日志搜索/过滤栏
+        ui.horizontal(|ui| {
+            ui.label("Filter:");
+            ui.add(
+                egui::TextEdit::singleline(&mut self.log_filter)
+                    .hint_text("substring or regex...")
+                    .desired_width(200.0),
+            );
+            ui.checkbox(&mut self.log_filter_regex, "Regex");
+
+            let stream_label = self
+                .log_filter_stream
+                .clone()
+                .unwrap_or_else(|| "All streams".to_string());
+            egui::ComboBox::from_id_source("log_filter_stream")
+                .selected_text(stream_label)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.log_filter_stream, None, "All streams");
+                    for service in &self.config.services {
+                        for suffix in ["stdout", "stderr"] {
+                            let tag = format!("{}-{}", service.display_name, suffix);
+                            ui.selectable_value(&mut self.log_filter_stream, Some(tag.clone()), tag);
+                        }
+                    }
+                });
+
+            let severity_label = self
+                .log_severity_filter
+                .map(|s| s.label())
+                .unwrap_or("All severities");
+            egui::ComboBox::from_id_source("log_filter_severity")
+                .selected_text(severity_label)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.log_severity_filter, None, "All severities");
+                    for severity in LogSeverity::ALL {
+                        ui.selectable_value(&mut self.log_severity_filter, Some(severity), severity.label());
+                    }
+                });
+
+            if ui.button(self.tr("logs.clear_filter")).clicked() {
+                self.log_filter.clear();
+                self.log_filter_regex = false;
+                self.log_filter_stream = None;
+                self.log_severity_filter = None;
+            }
+        });
+
+        let filter_active = !self.log_filter.is_empty()
+            || self.log_filter_stream.is_some()
+            || self.log_severity_filter.is_some();
+        // 过滤条件可能刚在上面这个过滤栏里被本帧的交互改过，这里再刷新一次掩码
+        // （若条件未变，这只是一次廉价的元组比较）
+        self.refresh_log_visibility_mask();
+
+        let accent = self.config.theme.palette().accent;
+        let monospace_bg = self.config.theme.palette().monospace_bg;
+
+        egui::Frame::none().fill(monospace_bg).show(ui, |ui| {
+            egui::ScrollArea::vertical()
+                .max_height(300.0)
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    ui.set_max_width(ui.available_width());
+
+                    if filter_active {
+                        // 过滤模式：逐行渲染已缓存的 LayoutJob，并在匹配区间上叠加高亮；
+                        // 可见性直接查 `log_visible_mask`，不再逐行重新判断过滤条件
+                        for ((line, job), visible) in self
+                            .logs
+                            .iter()
+                            .zip(self.log_jobs.iter())
+                            .zip(self.log_visible_mask.iter())
+                        {
+                            if !*visible {
+                                continue;
+                            }
+                            let ranges = Self::find_match_ranges(
+                                line,
+                                &self.log_filter,
+                                self.log_filter_regex,
+                            );
+                            let highlighted = Self::highlight_job(job, &ranges, accent);
+                            ui.add(egui::Label::new(highlighted).selectable(true));
+                        }
+
+                        // 过滤模式下，只有最新一行仍然匹配时才自动滚动到底部
+                        if self.logs_scroll_to_bottom && !self.user_selecting_logs {
+                            let newest_matches =
+                                self.log_visible_mask.last().copied().unwrap_or(false);
+                            if newest_matches {
+                                ui.scroll_to_cursor(None);
+                            }
+                            self.logs_scroll_to_bottom = false;
+                        }
+                    } else {
+                        // 未过滤：沿用可编辑 TextEdit 以支持长文本整体选中复制
+                        // TextEdit 需要一个 &mut String；为了不在用户选择时覆盖它，我们克隆一份到局部变量
+                        let mut local_text = self.logs_text.clone();
+
+                        // 将 TextEdit 放入 UI 并允许选择复制
+                        let text_edit = egui::TextEdit::multiline(&mut local_text)
+                            .desired_width(ui.available_width())
+                            .desired_rows(15);
+
+                        let response = ui.add(text_edit);
+
+                        // 如果用户在该区域按下鼠标左键，标记正在选择
+                        if response.ctx.input(|i| i.pointer.any_pressed()) && response.hovered() {
+                            self.user_selecting_logs = true;
+                        }
+
+                        // 当用户释放鼠标时，结束选择状态
+                        if response.ctx.input(|i| !i.pointer.any_down()) {
+                            // 只有当没有任何指针按下时才清除选择标志
+                            self.user_selecting_logs = false;
+                        }
 
-                // TextEdit 需要一个 &mut String；为了不在用户选择时覆盖它，我们克隆一份到局部变量
-                let mut local_text = self.logs_text.clone();
+                        // 自动滚动到末尾（只在非选择时）
+                        if self.logs_scroll_to_bottom && !self.user_selecting_logs {
+                            ui.scroll_to_cursor(None);
+                            self.logs_scroll_to_bottom = false;
+                        }
+                    }
+                });
+        });
+    }
 
-                // 将 TextEdit 放入 UI 并允许选择复制
-                let text_edit = egui::TextEdit::multiline(&mut local_text)
-                    .desired_width(ui.available_width())
-                    .desired_rows(15);
+    // 改进的 ANSI 解析函数 - 为换行优化，使用静态正则以避免重复编译；
+    // 实现完整的 SGR 语法（颜色/粗体/斜体/下划线/删除线，含 256 色与真彩色扩展）；
+    // 红/黄/绿/默认前景色取自当前主题，以保证在浅色主题下日志依旧可读
+    fn parse_ansi_to_layout_job(input: &str, theme: ThemeVariant) -> egui::text::LayoutJob {
+        use egui::text::LayoutJob;
+        use once_cell::sync::Lazy;
+        use regex::Regex;
 
-                let response = ui.add(text_edit);
+        // 标准 16 色 ANSI 调色板（30-37/90-97 前景色、40-47/100-107 背景色共用）
+        const ANSI_STANDARD: [egui::Color32; 8] = [
+            egui::Color32::from_rgb(0, 0, 0),
+            egui::Color32::from_rgb(170, 0, 0),
+            egui::Color32::from_rgb(0, 170, 0),
+            egui::Color32::from_rgb(170, 85, 0),
+            egui::Color32::from_rgb(0, 0, 170),
+            egui::Color32::from_rgb(170, 0, 170),
+            egui::Color32::from_rgb(0, 170, 170),
+            egui::Color32::from_rgb(170, 170, 170),
+        ];
+        const ANSI_BRIGHT: [egui::Color32; 8] = [
+            egui::Color32::from_rgb(85, 85, 85),
+            egui::Color32::from_rgb(255, 85, 85),
+            egui::Color32::from_rgb(85, 255, 85),
+            egui::Color32::from_rgb(255, 255, 85),
+            egui::Color32::from_rgb(85, 85, 255),
+            egui::Color32::from_rgb(255, 85, 255),
+            egui::Color32::from_rgb(85, 255, 255),
+            egui::Color32::from_rgb(255, 255, 255),
+        ];
 
-                // 如果用户在该区域按下鼠标左键，标记正在选择
-                if response.ctx.input(|i| i.pointer.any_pressed()) && response.hovered() {
-                    self.user_selecting_logs = true;
+        // 将 256 色调色板索引（0-255）解析为 `Color32`：0-15 标准色，16-231 是 6x6x6 色立方体，232-255 为灰阶
+        fn ansi_256_color(n: u32) -> egui::Color32 {
+            match n {
+                0..=7 => ANSI_STANDARD[n as usize],
+                8..=15 => ANSI_BRIGHT[(n - 8) as usize],
+                16..=231 => {
+                    let idx = n - 16;
+                    let r = idx / 36;
+                    let g = (idx / 6) % 6;
+                    let b = idx % 6;
+                    egui::Color32::from_rgb((r * 51) as u8, (g * 51) as u8, (b * 51) as u8)
                 }
+                _ => egui::Color32::from_gray((8 + (n - 232) * 10).min(255) as u8),
+            }
+        }
 
-                // 当用户释放鼠标时，结束选择状态
-                if response.ctx.input(|i| !i.pointer.any_down()) {
-                    // 只有当没有任何指针按下时才清除选择标志
-                    self.user_selecting_logs = false;
+        // SGR 渲染状态，随转义序列逐步更新，转换为当前文本段的 `TextFormat`；
+        // 携带主题的默认前景色与语义调色板，使 reset/红/黄/绿 始终落回当前主题
+        #[derive(Clone)]
+        struct SgrState {
+            fg: egui::Color32,
+            bg: Option<egui::Color32>,
+            bold: bool,
+            italic: bool,
+            underline: bool,
+            strikethrough: bool,
+            default_fg: egui::Color32,
+            palette: ThemePalette,
+        }
+
+        impl SgrState {
+            fn reset(default_fg: egui::Color32, palette: ThemePalette) -> Self {
+                SgrState {
+                    fg: default_fg,
+                    bg: None,
+                    bold: false,
+                    italic: false,
+                    underline: false,
+                    strikethrough: false,
+                    default_fg,
+                    palette,
                 }
+            }
 
-                // 只有当用户没有在选择时，才允许程序性的文本更新覆盖 UI
-                if !self.user_selecting_logs {
-                    // 将合并文本与 local_text 保持为最新（local_text 仅用于显示）
-                    // 如果 local_text 与 self.logs_text 不同，意味着用户可能编辑了它；但我们不会把编辑写回 self.logs_text
+            fn to_format(&self) -> egui::text::TextFormat {
+                let font_id = if self.bold {
+                    egui::FontId::monospace(12.0)
+                } else {
+                    egui::FontId::monospace(11.0)
+                };
+                egui::text::TextFormat {
+                    font_id,
+                    color: self.fg,
+                    background: self.bg.unwrap_or(egui::Color32::TRANSPARENT),
+                    italics: self.italic,
+                    underline: if self.underline {
+                        egui::Stroke::new(1.0, self.fg)
+                    } else {
+                        egui::Stroke::NONE
+                    },
+                    strikethrough: if self.strikethrough {
+                        egui::Stroke::new(1.0, self.fg)
+                    } else {
+                        egui::Stroke::NONE
+                    },
+                    ..Default::default()
                 }
+            }
 
-                // 自动滚动到末尾（只在非选择时）
-                if self.logs_scroll_to_bottom && !self.user_selecting_logs {
-                    ui.scroll_to_cursor(None);
-                    self.logs_scroll_to_bottom = false;
+            /// 按顺序应用一组 SGR 参数（`38`/`48` 会向前多消耗 2 或 4 个参数）
+            fn apply(&mut self, codes: &[u32]) {
+                let mut i = 0;
+                while i < codes.len() {
+                    match codes[i] {
+                        0 => *self = SgrState::reset(self.default_fg, self.palette),
+                        1 => self.bold = true,
+                        3 => self.italic = true,
+                        4 => self.underline = true,
+                        9 => self.strikethrough = true,
+                        22 => self.bold = false,
+                        23 => self.italic = false,
+                        24 => self.underline = false,
+                        29 => self.strikethrough = false,
+                        39 => self.fg = self.default_fg,
+                        49 => self.bg = None,
+                        // 31/32/33（红/绿/黄）取自主题调色板，其余标准色保持中性映射
+                        31 => self.fg = self.palette.error,
+                        32 => self.fg = self.palette.success,
+                        33 => self.fg = self.palette.warning,
+                        30..=37 => self.fg = ANSI_STANDARD[(codes[i] - 30) as usize],
+                        40..=47 => self.bg = Some(ANSI_STANDARD[(codes[i] - 40) as usize]),
+                        90..=97 => self.fg = ANSI_BRIGHT[(codes[i] - 90) as usize],
+                        100..=107 => self.bg = Some(ANSI_BRIGHT[(codes[i] - 100) as usize]),
+                        38 | 48 => {
+                            let is_fg = codes[i] == 38;
+                            match codes.get(i + 1) {
+                                Some(5) => {
+                                    if let Some(&n) = codes.get(i + 2) {
+                                        let color = ansi_256_color(n);
+                                        if is_fg {
+                                            self.fg = color;
+                                        } else {
+                                            self.bg = Some(color);
+                                        }
+                                        i += 2;
+                                    }
+                                }
+                                Some(2) => {
+                                    if let (Some(&r), Some(&g), Some(&b)) =
+                                        (codes.get(i + 2), codes.get(i + 3), codes.get(i + 4))
+                                    {
+                                        let color =
+                                            egui::Color32::from_rgb(r as u8, g as u8, b as u8);
+                                        if is_fg {
+                                            self.fg = color;
+                                        } else {
+                                            self.bg = Some(color);
+                                        }
+                                        i += 4;
+                                    }
+                                }
+                                _ => {}
+                            }
+                        }
+                        // 未知码按规范跳过，保留当前格式而非重置为白色
+                        _ => {}
+                    }
+                    i += 1;
                 }
-            });
-    }
+            }
+        }
 
-    // 改进的 ANSI 解析函数 - 为换行优化，使用静态正则以避免重复编译
-    fn parse_ansi_to_layout_job(input: &str) -> egui::text::LayoutJob {
-        use egui::text::{LayoutJob, TextFormat};
-        use once_cell::sync::Lazy;
-        use regex::Regex;
+        static ANSI_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\x1b\[([0-9;]*)m").unwrap());
 
-        static ANSI_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\x1b\[(\d+)(;\d+)*m").unwrap());
+        let palette = theme.palette();
+        let default_fg = theme.visuals().widgets.noninteractive.fg_stroke.color;
 
         let mut job = LayoutJob::default();
         job.wrap.max_width = f32::INFINITY;
         job.break_on_newline = true;
 
-        let mut current_color = egui::Color32::WHITE;
-
         // 快速路径：如果没有匹配，直接作为单一格式追加
         if !ANSI_RE.is_match(input) {
-            job.append(
-                input,
-                0.0,
-                TextFormat {
-                    color: current_color,
-                    font_id: egui::FontId::monospace(11.0),
-                    ..Default::default()
-                },
-            );
+            job.append(input, 0.0, SgrState::reset(default_fg, palette).to_format());
             return job;
         }
 
+        let mut state = SgrState::reset(default_fg, palette);
         let mut last_end = 0;
         for cap in ANSI_RE.captures_iter(input) {
             let m = cap.get(0).unwrap();
@@ -829,26 +2539,21 @@ impl MyApp {
             if start > last_end {
                 let text = &input[last_end..start];
                 if !text.is_empty() {
-                    job.append(
-                        text,
-                        0.0,
-                        TextFormat {
-                            color: current_color,
-                            font_id: egui::FontId::monospace(11.0),
-                            ..Default::default()
-                        },
-                    );
+                    job.append(text, 0.0, state.to_format());
                 }
             }
 
-            if let Some(code) = cap.get(1) {
-                match code.as_str() {
-                    "31" => current_color = egui::Color32::RED,
-                    "33" => current_color = egui::Color32::YELLOW,
-                    "32" => current_color = egui::Color32::GREEN,
-                    "0" => current_color = egui::Color32::WHITE,
-                    _ => {}
-                }
+            let params = cap.get(1).map(|p| p.as_str()).unwrap_or("");
+            let codes: Vec<u32> = params
+                .split(';')
+                .filter(|s| !s.is_empty())
+                .filter_map(|s| s.parse().ok())
+                .collect();
+            // 空参数列表（裸 `\x1b[m`）等价于一次重置
+            if codes.is_empty() {
+                state = SgrState::reset(default_fg, palette);
+            } else {
+                state.apply(&codes);
             }
 
             last_end = end;
@@ -857,66 +2562,554 @@ impl MyApp {
         if last_end < input.len() {
             let text = &input[last_end..];
             if !text.is_empty() {
-                job.append(
-                    text,
-                    0.0,
-                    TextFormat {
-                        color: current_color,
-                        font_id: egui::FontId::monospace(11.0),
-                        ..Default::default()
-                    },
-                );
+                job.append(text, 0.0, state.to_format());
             }
         }
 
-        job
-    }
+        job
+    }
+
+    /// 从形如 `[Node Server-stdout] ...` 的格式化日志行中提取流标签（不含方括号）
+    fn log_stream_tag(line: &str) -> Option<&str> {
+        let rest = line.strip_prefix('[')?;
+        let end = rest.find(']')?;
+        Some(&rest[..end])
+    }
+
+    /// 在 `line` 中查找 `filter` 的所有匹配区间（字节偏移），`use_regex` 决定按正则还是子串查找；
+    /// `filter` 为空时返回空列表（视为不过滤）
+    fn find_match_ranges(line: &str, filter: &str, use_regex: bool) -> Vec<std::ops::Range<usize>> {
+        if filter.is_empty() {
+            return Vec::new();
+        }
+        if use_regex {
+            return match regex::Regex::new(filter) {
+                Ok(re) => re.find_iter(line).map(|m| m.start()..m.end()).collect(),
+                Err(_) => Vec::new(),
+            };
+        }
+        let haystack = line.to_lowercase();
+        let needle = filter.to_lowercase();
+        if needle.is_empty() {
+            return Vec::new();
+        }
+        let mut ranges = Vec::new();
+        let mut start = 0;
+        while let Some(pos) = haystack[start..].find(&needle) {
+            let abs = start + pos;
+            ranges.push(abs..abs + needle.len());
+            start = abs + needle.len();
+            if start >= haystack.len() {
+                break;
+            }
+        }
+        ranges
+    }
+
+    /// 按 ERROR、WARN、INFO 的顺序在行内任意位置查找关键字（不区分大小写），都没有再看 ANSI 红/黄/绿颜色码来推断日志级别；两者都没有则视为无级别（不参与严重级别过滤）
+    fn log_line_severity(line: &str) -> Option<LogSeverity> {
+        let upper = line.to_uppercase();
+        if upper.contains("ERROR") {
+            return Some(LogSeverity::Error);
+        }
+        if upper.contains("WARN") {
+            return Some(LogSeverity::Warn);
+        }
+        if upper.contains("INFO") {
+            return Some(LogSeverity::Info);
+        }
+        if line.contains("\x1b[31m") {
+            return Some(LogSeverity::Error);
+        }
+        if line.contains("\x1b[33m") {
+            return Some(LogSeverity::Warn);
+        }
+        if line.contains("\x1b[32m") {
+            return Some(LogSeverity::Info);
+        }
+        None
+    }
+
+    /// 该行是否应在当前的流限定 + 严重级别 + 子串/正则过滤下可见
+    fn log_line_passes_filter(&self, line: &str) -> bool {
+        if let Some(stream) = &self.log_filter_stream {
+            if Self::log_stream_tag(line) != Some(stream.as_str()) {
+                return false;
+            }
+        }
+        if let Some(severity) = self.log_severity_filter {
+            if Self::log_line_severity(line) != Some(severity) {
+                return false;
+            }
+        }
+        if self.log_filter.is_empty() {
+            return true;
+        }
+        !Self::find_match_ranges(line, &self.log_filter, self.log_filter_regex).is_empty()
+    }
+
+    /// 保持 `log_visible_mask` 与 `logs` 同步：过滤条件变化时全量重算，
+    /// 否则只为新追加的行补算，避免每帧对全部日志重新跑一遍 `log_line_passes_filter`
+    fn refresh_log_visibility_mask(&mut self) {
+        let current_filter = (
+            self.log_filter.clone(),
+            self.log_filter_regex,
+            self.log_filter_stream.clone(),
+            self.log_severity_filter,
+        );
+
+        if current_filter != self.log_filter_snapshot || self.log_visible_mask.len() > self.logs.len() {
+            self.log_visible_mask = self.logs.iter().map(|line| self.log_line_passes_filter(line)).collect();
+            self.log_filter_snapshot = current_filter;
+            return;
+        }
+
+        if self.log_visible_mask.len() < self.logs.len() {
+            let start = self.log_visible_mask.len();
+            let mut appended: Vec<bool> = Vec::new();
+            for line in &self.logs[start..] {
+                appended.push(self.log_line_passes_filter(line));
+            }
+            self.log_visible_mask.extend(appended);
+        }
+    }
+
+    /// 让用户选择目标文件，把当前过滤视图（未过滤时为整个会话）写入 `.log`/`.txt`
+    fn export_logs(&mut self) {
+        let lines: Vec<&String> = self
+            .logs
+            .iter()
+            .filter(|line| self.log_line_passes_filter(line))
+            .collect();
+
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Log files", &["log", "txt"])
+            .set_file_name("webplayer-session.log")
+            .save_file()
+        else {
+            return;
+        };
+
+        let content = lines
+            .iter()
+            .map(|line| line.as_str())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        match fs::write(&path, content) {
+            Ok(_) => {
+                self.status_message = format!("Exported {} lines to {}", lines.len(), path.display());
+            }
+            Err(e) => {
+                self.status_message = format!("Failed to export logs: {}", e);
+            }
+        }
+    }
+
+    /// 在已缓存的 `job` 之上叠加高亮背景（使用当前主题的 `accent` 色），标出 `ranges` 覆盖的字节区间
+    fn highlight_job(
+        job: &egui::text::LayoutJob,
+        ranges: &[std::ops::Range<usize>],
+        accent: egui::Color32,
+    ) -> egui::text::LayoutJob {
+        if ranges.is_empty() {
+            return job.clone();
+        }
+        let mut new_job = egui::text::LayoutJob {
+            wrap: job.wrap.clone(),
+            break_on_newline: job.break_on_newline,
+            ..Default::default()
+        };
+        for section in &job.sections {
+            let start = section.byte_range.start;
+            let end = section.byte_range.end;
+            let mut points: Vec<usize> = vec![start, end];
+            for r in ranges {
+                if r.start > start && r.start < end {
+                    points.push(r.start);
+                }
+                if r.end > start && r.end < end {
+                    points.push(r.end);
+                }
+            }
+            points.sort_unstable();
+            points.dedup();
+            for w in points.windows(2) {
+                let (s, e) = (w[0], w[1]);
+                if s >= e {
+                    continue;
+                }
+                let mid = (s + e) / 2;
+                let highlighted = ranges.iter().any(|r| r.start <= mid && mid < r.end);
+                let mut format = section.format.clone();
+                if highlighted {
+                    format.background = accent;
+                }
+                new_job.append(&job.text[s..e], 0.0, format);
+            }
+        }
+        new_job
+    }
+
+    fn show_settings_ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if ui.button(self.tr("settings.save_config")).clicked() {
+                self.save_config();
+            }
+            if !self.status_message.is_empty() {
+                ui.label(&self.status_message);
+            }
+        });
+        ui.separator();
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            ui.collapsing(self.tr("settings.api_keys"), |ui| {
+                egui::Grid::new("api_keys_grid")
+                    .num_columns(2)
+                    .spacing([10.0, 4.0])
+                    .show(ui, |ui| {
+                        ui.label("MusicBrainz Client ID:");
+                        ui.add(egui::TextEdit::singleline(&mut self.config.api_keys.musicbrainz.client_id)
+                            .desired_width(200.0));
+                        ui.end_row();
+                        
+                        ui.label("MusicBrainz Client Secret:");
+                        ui.add(egui::TextEdit::singleline(&mut self.config.api_keys.musicbrainz.client_secret)
+                            .desired_width(200.0));
+                        ui.end_row();
+                        
+                        ui.label("MusicBrainz App Name:");
+                        ui.add(egui::TextEdit::singleline(&mut self.config.api_keys.musicbrainz.app_name)
+                            .desired_width(200.0));
+                        ui.end_row();
+                        
+                        ui.label("MusicBrainz App Version:");
+                        ui.add(egui::TextEdit::singleline(&mut self.config.api_keys.musicbrainz.app_version)
+                            .desired_width(200.0));
+                        ui.end_row();
+                        
+                        ui.label("TMDB API Key:");
+                        ui.add(egui::TextEdit::singleline(&mut self.config.api_keys.tmdb)
+                            .desired_width(200.0));
+                        ui.end_row();
+                    });
+            });
+
+            ui.collapsing("Appearance", |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Theme:");
+                    let mut changed = false;
+                    egui::ComboBox::from_id_source("theme_picker")
+                        .selected_text(self.config.theme.label())
+                        .show_ui(ui, |ui| {
+                            for variant in ThemeVariant::ALL {
+                                if ui
+                                    .selectable_value(&mut self.config.theme, variant, variant.label())
+                                    .clicked()
+                                {
+                                    changed = true;
+                                }
+                            }
+                        });
+                    if changed {
+                        ui.ctx().set_visuals(self.config.theme.visuals());
+                        // 主题切换后重新着色已缓存的日志行，而不必等待新日志到达
+                        self.log_jobs = self
+                            .logs
+                            .iter()
+                            .map(|line| Self::parse_ansi_to_layout_job(line, self.config.theme))
+                            .collect();
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("Language:");
+                    let mut language_changed = false;
+                    egui::ComboBox::from_id_source("language_picker")
+                        .selected_text(self.config.ui_language.label())
+                        .show_ui(ui, |ui| {
+                            for language in Language::ALL {
+                                if ui
+                                    .selectable_value(
+                                        &mut self.config.ui_language,
+                                        language,
+                                        language.label(),
+                                    )
+                                    .clicked()
+                                {
+                                    language_changed = true;
+                                }
+                            }
+                        });
+                    if language_changed {
+                        // 重新加载译文表：优先读 ./src/locales/<code>.json，缺失或解析失败时用打包的默认表；
+                        // 只有 `Language` 中已有的语言才会被加载
+                        self.locale = Locale::load(self.config.ui_language);
+                    }
+                });
+            });
+
+            ui.collapsing("Network", |ui| {
+                ui.label(
+                    "Outbound HTTP requests (network check, Check Proxy, model test) go through this \
+                     proxy when enabled. Format: host:port.",
+                );
+                egui::Grid::new("proxy_grid")
+                    .num_columns(2)
+                    .spacing([10.0, 4.0])
+                    .show(ui, |ui| {
+                        ui.label("Enable Proxy:");
+                        ui.checkbox(&mut self.config.proxy.enabled, "");
+                        ui.end_row();
+
+                        ui.label("Proxy URL:");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.config.proxy.url)
+                                .desired_width(200.0)
+                                .hint_text("host:port"),
+                        );
+                        ui.end_row();
+
+                        ui.label("Proxy Username:");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.config.proxy.username)
+                                .desired_width(200.0),
+                        );
+                        ui.end_row();
+
+                        ui.label("Proxy Password:");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.config.proxy.password)
+                                .password(true)
+                                .desired_width(200.0),
+                        );
+                        ui.end_row();
+                    });
+            });
+
+            ui.collapsing("Profiles", |ui| {
+                ui.label(
+                    "Snapshot the current configuration under a name, or switch to a previously \
+                     saved one — lets you keep separate setups (e.g. local GGUF vs. online API) \
+                     without hand-editing config.json.",
+                );
+                ui.add_space(4.0);
+
+                ui.horizontal(|ui| {
+                    ui.label("Profile name:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.profile_name_input)
+                            .desired_width(150.0),
+                    );
+                    if ui.button("Save Profile As...").clicked() {
+                        let name = self.profile_name_input.clone();
+                        self.save_profile_as(&name);
+                    }
+                });
+                ui.add_space(4.0);
+
+                ui.label("Saved profiles:");
+                for profile in self.list_profiles() {
+                    ui.horizontal(|ui| {
+                        ui.label(&profile);
+                        if ui.button("Load").clicked() {
+                            self.load_profile(&profile);
+                        }
+                    });
+                }
+                ui.add_space(4.0);
+
+                ui.horizontal(|ui| {
+                    if ui.button("Export...").clicked() {
+                        self.export_profile();
+                    }
+                    if ui.button("Import...").clicked() {
+                        self.import_profile();
+                    }
+                });
+            });
+
+            ui.collapsing(self.tr("settings.services"), |ui| {
+                ui.label(
+                    "Declarative process definitions — add an entry to manage a third backend \
+                     without recompiling.",
+                );
+                ui.add_space(4.0);
+
+                let mut remove_idx: Option<usize> = None;
+                for (i, svc) in self.config.services.iter_mut().enumerate() {
+                    ui.push_id(i, |ui| {
+                        ui.group(|ui| {
+                            egui::Grid::new("service_grid")
+                                .num_columns(2)
+                                .spacing([10.0, 4.0])
+                                .show(ui, |ui| {
+                                    ui.label("Display Name:");
+                                    ui.add(
+                                        egui::TextEdit::singleline(&mut svc.display_name)
+                                            .desired_width(150.0),
+                                    );
+                                    ui.end_row();
+
+                                    ui.label("Program:");
+                                    ui.add(
+                                        egui::TextEdit::singleline(&mut svc.program)
+                                            .desired_width(150.0),
+                                    );
+                                    ui.end_row();
+
+                                    ui.label("Args (space-separated):");
+                                    let mut args_text = svc.args.join(" ");
+                                    if ui
+                                        .add(
+                                            egui::TextEdit::singleline(&mut args_text)
+                                                .desired_width(200.0),
+                                        )
+                                        .changed()
+                                    {
+                                        svc.args =
+                                            args_text.split_whitespace().map(String::from).collect();
+                                    }
+                                    ui.end_row();
+
+                                    ui.label("Working Dir:");
+                                    let mut working_dir = svc.working_dir.clone().unwrap_or_default();
+                                    if ui
+                                        .add(
+                                            egui::TextEdit::singleline(&mut working_dir)
+                                                .desired_width(150.0),
+                                        )
+                                        .changed()
+                                    {
+                                        svc.working_dir = if working_dir.is_empty() {
+                                            None
+                                        } else {
+                                            Some(working_dir)
+                                        };
+                                    }
+                                    ui.end_row();
+
+                                    ui.label("Env (KEY=VAL, space-separated):");
+                                    let mut env_text = svc
+                                        .env
+                                        .iter()
+                                        .map(|(k, v)| format!("{}={}", k, v))
+                                        .collect::<Vec<_>>()
+                                        .join(" ");
+                                    if ui
+                                        .add(
+                                            egui::TextEdit::singleline(&mut env_text)
+                                                .desired_width(200.0),
+                                        )
+                                        .changed()
+                                    {
+                                        svc.env = env_text
+                                            .split_whitespace()
+                                            .filter_map(|kv| kv.split_once('='))
+                                            .map(|(k, v)| (k.to_string(), v.to_string()))
+                                            .collect();
+                                    }
+                                    ui.end_row();
+
+                                    ui.label("Readiness Host:");
+                                    ui.add(
+                                        egui::TextEdit::singleline(&mut svc.readiness_host)
+                                            .desired_width(150.0),
+                                    );
+                                    ui.end_row();
+
+                                    ui.label("Readiness Port:");
+                                    ui.add(egui::DragValue::new(&mut svc.readiness_port));
+                                    ui.end_row();
+
+                                    ui.label("Auto-restart by default:");
+                                    ui.checkbox(&mut svc.auto_restart, "");
+                                    ui.end_row();
+
+                                    ui.label("Dependency Manifest:");
+                                    let mut manifest = svc.dependency_manifest.clone().unwrap_or_default();
+                                    if ui
+                                        .add(
+                                            egui::TextEdit::singleline(&mut manifest)
+                                                .hint_text("requirements.txt / package.json")
+                                                .desired_width(180.0),
+                                        )
+                                        .changed()
+                                    {
+                                        svc.dependency_manifest = if manifest.is_empty() {
+                                            None
+                                        } else {
+                                            Some(manifest)
+                                        };
+                                    }
+                                    ui.end_row();
 
-    fn show_settings_ui(&mut self, ui: &mut egui::Ui) {
-        ui.horizontal(|ui| {
-            if ui.button("Save Config").clicked() {
-                self.save_config();
-            }
-            if !self.status_message.is_empty() {
-                ui.label(&self.status_message);
-            }
-        });
-        ui.separator();
+                                    ui.label("Dependency Install Cmd:");
+                                    let mut install_cmd = svc
+                                        .dependency_install_cmd
+                                        .clone()
+                                        .unwrap_or_default()
+                                        .join(" ");
+                                    if ui
+                                        .add(
+                                            egui::TextEdit::singleline(&mut install_cmd)
+                                                .hint_text("pip install -r requirements.txt")
+                                                .desired_width(220.0),
+                                        )
+                                        .changed()
+                                    {
+                                        svc.dependency_install_cmd = if install_cmd.trim().is_empty() {
+                                            None
+                                        } else {
+                                            Some(
+                                                install_cmd
+                                                    .split_whitespace()
+                                                    .map(String::from)
+                                                    .collect(),
+                                            )
+                                        };
+                                    }
+                                    ui.end_row();
+                                });
+                            if ui.small_button("Remove").clicked() {
+                                remove_idx = Some(i);
+                            }
+                        });
+                    });
+                    ui.add_space(4.0);
+                }
 
-        egui::ScrollArea::vertical().show(ui, |ui| {
-            ui.collapsing("API Keys", |ui| {
-                egui::Grid::new("api_keys_grid")
-                    .num_columns(2)
-                    .spacing([10.0, 4.0])
-                    .show(ui, |ui| {
-                        ui.label("MusicBrainz Client ID:");
-                        ui.add(egui::TextEdit::singleline(&mut self.config.api_keys.musicbrainz.client_id)
-                            .desired_width(200.0));
-                        ui.end_row();
-                        
-                        ui.label("MusicBrainz Client Secret:");
-                        ui.add(egui::TextEdit::singleline(&mut self.config.api_keys.musicbrainz.client_secret)
-                            .desired_width(200.0));
-                        ui.end_row();
-                        
-                        ui.label("MusicBrainz App Name:");
-                        ui.add(egui::TextEdit::singleline(&mut self.config.api_keys.musicbrainz.app_name)
-                            .desired_width(200.0));
-                        ui.end_row();
-                        
-                        ui.label("MusicBrainz App Version:");
-                        ui.add(egui::TextEdit::singleline(&mut self.config.api_keys.musicbrainz.app_version)
-                            .desired_width(200.0));
-                        ui.end_row();
-                        
-                        ui.label("TMDB API Key:");
-                        ui.add(egui::TextEdit::singleline(&mut self.config.api_keys.tmdb)
-                            .desired_width(200.0));
-                        ui.end_row();
+                if let Some(i) = remove_idx {
+                    self.config.services.remove(i);
+                    // 对应的运行时状态一并移除；若该服务仍在运行，用户需先手动停止它
+                    if i < self.services.len() {
+                        self.services.remove(i);
+                    }
+                }
+
+                if ui.button(self.tr("settings.add_service")).clicked() {
+                    self.config.services.push(ServiceConfig {
+                        id: format!("service-{}", self.config.services.len()),
+                        display_name: "New Service".to_string(),
+                        program: String::new(),
+                        args: Vec::new(),
+                        working_dir: None,
+                        env: std::collections::HashMap::new(),
+                        watch_path: None,
+                        readiness_host: "127.0.0.1".to_string(),
+                        readiness_port: 0,
+                        auto_restart: default_auto_restart(),
+                        dependency_manifest: None,
+                        dependency_install_cmd: None,
                     });
+                    self.services.push(Arc::new(Mutex::new(ProcessState {
+                        auto_restart: default_auto_restart(),
+                        ..ProcessState::default()
+                    })));
+                }
             });
 
-            ui.collapsing("Media Directories", |ui| {
+            ui.collapsing(self.tr("settings.media_directories"), |ui| {
                 let mut dir_to_remove: Option<usize> = None;
                 let mut dir_move_up: Option<usize> = None;
                 let mut dir_move_down: Option<usize> = None;
@@ -963,7 +3156,7 @@ impl MyApp {
                     self.config.media_directories.remove(i);
                 }
 
-                if ui.button("Add Directory").clicked() {
+                if ui.button(self.tr("settings.add_directory")).clicked() {
                     self.config.media_directories.push(MediaDirectory {
                         alias: "New Alias".to_string(),
                         path: "New Path".to_string(),
@@ -971,7 +3164,7 @@ impl MyApp {
                 }
             });
 
-            ui.collapsing("Models", |ui| {
+            ui.collapsing(self.tr("settings.models"), |ui| {
                 let mut model_to_remove: Option<usize> = None;
                 let mut model_move_up: Option<usize> = None;
                 let mut model_move_down: Option<usize> = None;
@@ -1182,15 +3375,127 @@ impl MyApp {
                 if let Some(i) = model_to_remove {
                     self.config.models.remove(i);
                 }
-                if ui.button("Add Model").clicked() {
+                if ui.button(self.tr("settings.add_model")).clicked() {
                     self.config.models.push(Model::default());
                 }
             });
 
-            ui.collapsing("Transcriber Models", |ui| {
+            ui.collapsing("Compare Models", |ui| {
+                self.compare_selected.resize(self.config.models.len(), false);
+
+                ui.label(
+                    "Paste a subtitle sample (one line per row) and dispatch it concurrently \
+                     to the selected models to compare their output side by side.",
+                );
+                ui.add_space(4.0);
+
+                ui.label("Models to compare:");
+                ui.horizontal_wrapped(|ui| {
+                    for (i, model) in self.config.models.iter().enumerate() {
+                        ui.checkbox(&mut self.compare_selected[i], model.model_path.clone());
+                    }
+                });
+                ui.add_space(4.0);
+
+                ui.label("Sample text:");
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.compare_input)
+                        .desired_width(ui.available_width() - 20.0)
+                        .desired_rows(4),
+                );
+                ui.add_space(4.0);
+
+                let running = {
+                    let results = self.compare_results.lock().unwrap();
+                    self.compare_expected > 0 && results.len() < self.compare_expected
+                };
+                ui.add_enabled_ui(!running, |ui| {
+                    if ui.button("Run Comparison").clicked() {
+                        self.run_model_comparison(ui.ctx().clone());
+                    }
+                });
+                if running {
+                    ui.label(format!(
+                        "Running... {}/{} models finished",
+                        self.compare_results.lock().unwrap().len(),
+                        self.compare_expected
+                    ));
+                }
+
+                let results = self.compare_results.lock().unwrap().clone();
+                if !results.is_empty() {
+                    ui.add_space(8.0);
+                    ui.separator();
+                    egui::ScrollArea::horizontal().show(ui, |ui| {
+                        egui::Grid::new("compare_results_grid")
+                            .num_columns(results.len() + 1)
+                            .spacing([12.0, 4.0])
+                            .striped(true)
+                            .show(ui, |ui| {
+                                ui.label("Line");
+                                for result in &results {
+                                    let header = match result.token_count {
+                                        Some(tokens) => format!(
+                                            "{}\n{}ms · {} tok",
+                                            result.model_path, result.latency_ms, tokens
+                                        ),
+                                        None => format!("{}\n{}ms", result.model_path, result.latency_ms),
+                                    };
+                                    ui.label(header);
+                                }
+                                ui.end_row();
+
+                                if let Some(error) = results.iter().find_map(|r| r.error.as_ref()) {
+                                    ui.label("error");
+                                    for result in &results {
+                                        ui.colored_label(
+                                            self.config.theme.palette().error,
+                                            result.error.as_deref().unwrap_or("-"),
+                                        );
+                                    }
+                                    ui.end_row();
+                                    let _ = error;
+                                }
+
+                                let max_lines = results
+                                    .iter()
+                                    .map(|r| r.translations.len())
+                                    .max()
+                                    .unwrap_or(0);
+                                for line_idx in 0..max_lines {
+                                    ui.label(format!("{}", line_idx + 1));
+                                    let first = results
+                                        .first()
+                                        .and_then(|r| r.translations.get(line_idx))
+                                        .map(String::as_str);
+                                    for result in &results {
+                                        let text = result
+                                            .translations
+                                            .get(line_idx)
+                                            .map(String::as_str)
+                                            .unwrap_or("");
+                                        // 与第一个参与模型的该行结果不同则高亮为警告色，标出分歧点
+                                        if first.is_some() && Some(text) != first {
+                                            ui.colored_label(
+                                                self.config.theme.palette().warning,
+                                                text,
+                                            );
+                                        } else {
+                                            ui.label(text);
+                                        }
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    });
+                }
+            });
+
+            ui.collapsing(self.tr("settings.transcriber_models"), |ui| {
                 let mut t_remove: Option<usize> = None;
                 let mut t_move_up: Option<usize> = None;
                 let mut t_move_down: Option<usize> = None;
+                let max_chars_label = self.tr("transcriber.max_chars_per_line");
 
                 for (i, tmodel) in &mut self.config.transcriber_models.iter_mut().enumerate() {
                     egui::CollapsingHeader::new(format!("{}: {}", tmodel.model_source, tmodel.model))
@@ -1216,7 +3521,7 @@ impl MyApp {
                         });
 
                         ui.horizontal(|ui| {
-                            ui.label("Max Chars/Line:");
+                            ui.label(&max_chars_label);
                             ui.add(egui::DragValue::new(&mut tmodel.max_chars_per_line).speed(1));
                             ui.checkbox(&mut tmodel.dense_subtitles, "Dense Subtitles");
                         });
@@ -1243,7 +3548,7 @@ impl MyApp {
                     self.config.transcriber_models.remove(i);
                 }
 
-                if ui.button("Add Transcriber Model").clicked() {
+                if ui.button(self.tr("settings.add_transcriber_model")).clicked() {
                     self.config.transcriber_models.push(TranscriberModel::default());
                 }
             });
@@ -1251,10 +3556,10 @@ impl MyApp {
     }
 
     fn show_env_check_ui(&mut self, ui: &mut egui::Ui) {
-        ui.heading("Environment Health Check");
+        ui.heading(self.tr("env.heading"));
         ui.add_space(10.0);
 
-        if ui.button("Run Checks").clicked() {
+        if ui.button(self.tr("env.run_checks")).clicked() {
             self.run_environment_checks(ui.ctx().clone());
         }
 
@@ -1273,10 +3578,10 @@ impl MyApp {
                     }
 
                     let (label, color) = match result.status {
-                        CheckStatus::Pending => ("Pending", egui::Color32::GRAY),
-                        CheckStatus::Checking => ("Checking...", egui::Color32::LIGHT_BLUE),
-                        CheckStatus::Success => ("✅ Found", egui::Color32::GREEN),
-                        CheckStatus::Failure => ("❌ Not Found", egui::Color32::RED),
+                        CheckStatus::Pending => (self.tr("status.pending"), egui::Color32::GRAY),
+                        CheckStatus::Checking => (self.tr("status.checking"), egui::Color32::LIGHT_BLUE),
+                        CheckStatus::Success => (self.tr("status.found"), egui::Color32::GREEN),
+                        CheckStatus::Failure => (self.tr("status.not_found"), egui::Color32::RED),
                     };
                     ui.label(&result.name);
                     ui.label(egui::RichText::new(label).color(color));
@@ -1287,12 +3592,15 @@ impl MyApp {
 
 
     fn show_network_check_ui(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
-        ui.heading("Network Accessibility Check");
+        ui.heading(self.tr("network.heading"));
         ui.add_space(10.0);
 
-        if ui.button("Run Network Checks").clicked() {
-            self.run_network_checks_parallel(ctx.clone());
-        }
+        ui.horizontal(|ui| {
+            if ui.button(self.tr("network.run_checks")).clicked() {
+                self.run_network_checks_parallel(ctx.clone());
+            }
+            ui.checkbox(&mut self.network_check_bypass_proxy, "Bypass proxy");
+        });
 
         ui.add_space(10.0);
 
@@ -1302,10 +3610,10 @@ impl MyApp {
             .show(ui, |ui| {
                 for result in results.iter() {
                     let (label, color) = match result.status {
-                        CheckStatus::Pending => ("Pending", egui::Color32::GRAY),
-                        CheckStatus::Checking => ("Checking...", egui::Color32::LIGHT_BLUE),
-                        CheckStatus::Success => ("✅ Accessible", egui::Color32::GREEN),
-                        CheckStatus::Failure => ("❌ Inaccessible", egui::Color32::RED),
+                        CheckStatus::Pending => (self.tr("status.pending"), egui::Color32::GRAY),
+                        CheckStatus::Checking => (self.tr("status.checking"), egui::Color32::LIGHT_BLUE),
+                        CheckStatus::Success => (self.tr("status.accessible"), egui::Color32::GREEN),
+                        CheckStatus::Failure => (self.tr("status.inaccessible"), egui::Color32::RED),
                     };
                     ui.label(&result.url);
                     // 如果有延迟信息则显示为 "label (123ms)"
@@ -1317,6 +3625,33 @@ impl MyApp {
                     ui.end_row();
                 }
             });
+        drop(results);
+
+        ui.add_space(10.0);
+        ui.separator();
+        ui.add_space(10.0);
+
+        ui.horizontal(|ui| {
+            if ui.button("Check Proxy").clicked() {
+                self.check_proxy(ctx.clone());
+            }
+        });
+
+        let proxy_result = self.proxy_check_result.lock().unwrap().clone();
+        let (label, color) = match proxy_result.status {
+            CheckStatus::Pending => (self.tr("status.pending"), egui::Color32::GRAY),
+            CheckStatus::Checking => (self.tr("status.checking"), egui::Color32::LIGHT_BLUE),
+            CheckStatus::Success => (self.tr("status.accessible"), egui::Color32::GREEN),
+            CheckStatus::Failure => (self.tr("status.inaccessible"), egui::Color32::RED),
+        };
+        let mut proxy_label = label.to_string();
+        if let Some(ip) = &proxy_result.exit_ip {
+            proxy_label = format!("{} — exit IP: {}", proxy_label, ip);
+        }
+        if let Some(lat) = proxy_result.latency_ms {
+            proxy_label = format!("{} ({} ms)", proxy_label, lat);
+        }
+        ui.label(egui::RichText::new(proxy_label).color(color));
     }
 }
 
@@ -1324,6 +3659,8 @@ impl MyApp {
 
 impl eframe::App for MyApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.process_watch_events();
+
         // 批量处理日志消息，避免频繁重绘 —— 只解析并缓存新增日志
         let mut new_logs = Vec::new();
         while let Ok(log_line) = self.log_receiver.try_recv() {
@@ -1342,7 +3679,7 @@ impl eframe::App for MyApp {
                 self.logs_text.push_str(&log);
 
                 // parse and cache layout job
-                let job = Self::parse_ansi_to_layout_job(&log);
+                let job = Self::parse_ansi_to_layout_job(&log, self.config.theme);
                 self.log_jobs.push(job);
             }
             // 只有在用户没有在进行选择时才自动滚动
@@ -1354,16 +3691,20 @@ impl eframe::App for MyApp {
             const MAX_LOG_LINES: usize = 1000;
             const MAX_LOG_CHARS: usize = 200_000; // 大约 200KB of text
 
-            // 裁剪到最大行数（同时裁剪 log_jobs）
+            // 裁剪到最大行数（同时裁剪 log_jobs 与 log_visible_mask）
             if self.logs.len() > MAX_LOG_LINES {
                 let excess = self.logs.len() - MAX_LOG_LINES;
                 self.logs.drain(0..excess);
                 self.log_jobs.drain(0..excess);
+                // log_visible_mask 可能还没来得及为本帧新追加的行补算，长度不一定等于 logs，
+                // 所以只裁掉掩码里确实存在的那部分
+                let mask_excess = excess.min(self.log_visible_mask.len());
+                self.log_visible_mask.drain(0..mask_excess);
                 // 重建合并文本以保持一致性（裁剪发生频率低）
                 self.logs_text = self.logs.join("\n");
             }
 
-            // 如果字符总数仍然过大，则继续从头部删除直到符合限制（同时裁剪 log_jobs）
+            // 如果字符总数仍然过大，则继续从头部删除直到符合限制（同时裁剪 log_jobs 与 log_visible_mask）
             let mut total_chars: usize = self.logs.iter().map(|s| s.len()).sum();
             while total_chars > MAX_LOG_CHARS && !self.logs.is_empty() {
                 if let Some(removed) = self.logs.get(0) {
@@ -1371,26 +3712,64 @@ impl eframe::App for MyApp {
                 }
                 self.logs.remove(0);
                 self.log_jobs.remove(0);
+                if !self.log_visible_mask.is_empty() {
+                    self.log_visible_mask.remove(0);
+                }
                 // 同步合并文本
                 self.logs_text = self.logs.join("\n");
             }
         }
 
 
+        let tab_launcher_label = self.tr("tab.launcher");
+        let tab_settings_label = self.tr("tab.settings");
+        let tab_env_check_label = self.tr("tab.environment_check");
+        let tab_network_check_label = self.tr("tab.network_check");
+        let tab_model_test_label = self.tr("tab.model_test");
+
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.horizontal(|ui| {
-                ui.selectable_value(&mut self.active_tab, AppTab::Launcher, "Launcher");
-                ui.selectable_value(&mut self.active_tab, AppTab::Settings, "Settings");
+                ui.selectable_value(&mut self.active_tab, AppTab::Launcher, tab_launcher_label);
+                ui.selectable_value(&mut self.active_tab, AppTab::Settings, tab_settings_label);
                 ui.selectable_value(
                     &mut self.active_tab,
                     AppTab::EnvironmentCheck,
-                    "Environment Check",
+                    tab_env_check_label,
                 );
                 ui.selectable_value(
                     &mut self.active_tab,
                     AppTab::NetworkCheck,
-                    "Network Check",
+                    tab_network_check_label,
                 );
+                ui.selectable_value(
+                    &mut self.active_tab,
+                    AppTab::ModelTest,
+                    tab_model_test_label,
+                );
+
+                // 全局语言切换：与 Settings -> Appearance 中的下拉框共享同一个 config.ui_language
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    let mut language_changed = false;
+                    egui::ComboBox::from_id_source("tab_bar_language_picker")
+                        .selected_text(self.config.ui_language.label())
+                        .show_ui(ui, |ui| {
+                            for language in Language::ALL {
+                                if ui
+                                    .selectable_value(
+                                        &mut self.config.ui_language,
+                                        language,
+                                        language.label(),
+                                    )
+                                    .clicked()
+                                {
+                                    language_changed = true;
+                                }
+                            }
+                        });
+                    if language_changed {
+                        self.locale = Locale::load(self.config.ui_language);
+                    }
+                });
             });
             ui.separator();
 
@@ -1399,21 +3778,42 @@ impl eframe::App for MyApp {
                 AppTab::Settings => self.show_settings_ui(ui),
                 AppTab::EnvironmentCheck => self.show_env_check_ui(ui),
                 AppTab::NetworkCheck => self.show_network_check_ui(ui, ctx),
+                AppTab::ModelTest => self.show_model_test_ui(ui, ctx),
             }
         });
 
-        let node_running = self.node_server.lock().unwrap().child.is_some();
-        let python_running = self.python_server.lock().unwrap().child.is_some();
-        if node_running || python_running {
-            // 降低重绘频率
-            ctx.request_repaint_after(std::time::Duration::from_millis(500));
-        }
+        // 持续轮询以便及时拾取文件监听事件（config.json 重载在服务未运行时也要生效）
+        ctx.request_repaint_after(std::time::Duration::from_millis(500));
     }
 }
 
 impl MyApp {
+    /// 按 `proxy` 配置构造一个 ureq agent；`bypass` 为 true，或代理未启用/URL 为空时，
+    /// 返回一个不经代理的 agent
+    fn build_http_agent(
+        proxy: &ProxyConfig,
+        bypass: bool,
+        timeout: std::time::Duration,
+    ) -> ureq::Agent {
+        let mut builder = ureq::AgentBuilder::new().timeout(timeout);
+        if proxy.enabled && !bypass && !proxy.url.is_empty() {
+            let proxy_addr = if !proxy.username.is_empty() {
+                format!("{}:{}@{}", proxy.username, proxy.password, proxy.url)
+            } else {
+                proxy.url.clone()
+            };
+            match ureq::Proxy::new(&proxy_addr) {
+                Ok(p) => builder = builder.proxy(p),
+                Err(e) => eprintln!("Invalid proxy configuration '{}': {}", proxy.url, e),
+            }
+        }
+        builder.build()
+    }
+
     fn run_network_checks_parallel(&mut self, ctx: egui::Context) {
         let results_arc = Arc::clone(&self.network_check_results);
+        let proxy = self.config.proxy.clone();
+        let bypass_proxy = self.network_check_bypass_proxy;
 
         {
             let mut results = results_arc.lock().unwrap();
@@ -1433,9 +3833,7 @@ impl MyApp {
 
         thread::spawn(move || {
             use std::sync::mpsc;
-            let agent = ureq::AgentBuilder::new()
-                .timeout(std::time::Duration::from_secs(10))
-                .build();
+            let agent = Self::build_http_agent(&proxy, bypass_proxy, std::time::Duration::from_secs(10));
 
             let (tx, rx) = mpsc::channel();
 
@@ -1471,6 +3869,345 @@ impl MyApp {
             }
         });
     }
+
+    /// 经 `config.proxy`（若已启用）请求一个 IP 回显端点，汇报出口 IP 与往返延迟，
+    /// 写入 `proxy_check_result`；始终走代理，不受 `network_check_bypass_proxy` 开关影响
+    fn check_proxy(&mut self, ctx: egui::Context) {
+        let proxy = self.config.proxy.clone();
+        let result_arc = Arc::clone(&self.proxy_check_result);
+
+        {
+            let mut result = result_arc.lock().unwrap();
+            result.status = CheckStatus::Checking;
+        }
+        ctx.request_repaint();
+
+        let ctx_clone = ctx.clone();
+        thread::spawn(move || {
+            let agent = Self::build_http_agent(&proxy, false, std::time::Duration::from_secs(10));
+            let start = std::time::Instant::now();
+            let exit_ip = agent
+                .get("https://api.ipify.org?format=json")
+                .call()
+                .ok()
+                .and_then(|response| response.into_string().ok())
+                .and_then(|body| serde_json::from_str::<serde_json::Value>(&body).ok())
+                .and_then(|value| value.get("ip").and_then(|ip| ip.as_str()).map(|s| s.to_string()));
+            let elapsed = start.elapsed().as_millis();
+
+            let mut result = result_arc.lock().unwrap();
+            match exit_ip {
+                Some(ip) => {
+                    result.status = CheckStatus::Success;
+                    result.exit_ip = Some(ip);
+                    result.latency_ms = Some(elapsed);
+                }
+                None => {
+                    result.status = CheckStatus::Failure;
+                    result.exit_ip = None;
+                    result.latency_ms = None;
+                }
+            }
+            drop(result);
+            ctx_clone.request_repaint();
+        });
+    }
+
+    /// 把 `compare_input` 按行拆开，并发分发给 `models` 中每个被选中的模型做翻译，
+    /// 结果（含延迟与 token 数）写回 `compare_results` 供结果面板按行做差异对比
+    fn run_model_comparison(&mut self, ctx: egui::Context) {
+        let lines: Vec<String> = self
+            .compare_input
+            .lines()
+            .map(|l| l.to_string())
+            .collect();
+        if lines.is_empty() {
+            self.status_message = "Compare Models: input is empty".to_string();
+            return;
+        }
+
+        let models: Vec<Model> = self
+            .config
+            .models
+            .iter()
+            .zip(self.compare_selected.iter())
+            .filter(|(_, selected)| **selected)
+            .map(|(model, _)| model.clone())
+            .collect();
+        if models.is_empty() {
+            self.status_message = "Compare Models: select at least one model".to_string();
+            return;
+        }
+
+        // Python 后端负责实际的模型推理；沿用 `default_services` 里同一套约定的 host/port
+        let (host, port) = self
+            .config
+            .services
+            .iter()
+            .find(|svc| svc.id == "python")
+            .map(|svc| (svc.readiness_host.clone(), svc.readiness_port))
+            .unwrap_or(("127.0.0.1".to_string(), 8000));
+
+        self.compare_expected = models.len();
+        {
+            let mut results = self.compare_results.lock().unwrap();
+            results.clear();
+        }
+        ctx.request_repaint();
+
+        let results_arc = Arc::clone(&self.compare_results);
+        let ctx_clone = ctx.clone();
+        let log_sender = self.log_sender.clone();
+
+        thread::spawn(move || {
+            use std::sync::mpsc;
+            let agent = ureq::AgentBuilder::new()
+                .timeout(std::time::Duration::from_secs(120))
+                .build();
+
+            let (tx, rx) = mpsc::channel();
+
+            for model in models {
+                let tx = tx.clone();
+                let agent = agent.clone();
+                let lines = lines.clone();
+                let url = format!("http://{}:{}/compare_translate", host, port);
+                let log_sender = log_sender.clone();
+                thread::spawn(move || {
+                    let start = std::time::Instant::now();
+                    let payload = serde_json::json!({
+                        "model_path": model.model_path,
+                        "generation_config": model.generation_config,
+                        "lines": lines,
+                    });
+                    let result = match agent.post(&url).send_json(payload) {
+                        Ok(response) => match response.into_json::<serde_json::Value>() {
+                            Ok(body) => ModelComparisonResult {
+                                model_path: model.model_path.clone(),
+                                translations: body["translations"]
+                                    .as_array()
+                                    .map(|arr| {
+                                        arr.iter()
+                                            .map(|v| v.as_str().unwrap_or("").to_string())
+                                            .collect()
+                                    })
+                                    .unwrap_or_default(),
+                                latency_ms: start.elapsed().as_millis(),
+                                token_count: body["token_count"].as_u64(),
+                                error: None,
+                            },
+                            Err(e) => ModelComparisonResult {
+                                model_path: model.model_path.clone(),
+                                translations: Vec::new(),
+                                latency_ms: start.elapsed().as_millis(),
+                                token_count: None,
+                                error: Some(format!("Invalid response: {}", e)),
+                            },
+                        },
+                        Err(e) => ModelComparisonResult {
+                            model_path: model.model_path.clone(),
+                            translations: Vec::new(),
+                            latency_ms: start.elapsed().as_millis(),
+                            token_count: None,
+                            error: Some(e.to_string()),
+                        },
+                    };
+                    let _ = log_sender.send(format!(
+                        "[compare] {} finished in {}ms",
+                        result.model_path, result.latency_ms
+                    ));
+                    let _ = tx.send(result);
+                });
+            }
+
+            drop(tx);
+
+            for result in rx {
+                {
+                    let mut results = results_arc.lock().unwrap();
+                    results.push(result);
+                }
+                ctx_clone.request_repaint();
+            }
+        });
+    }
+
+    /// 对 `config.models` 中每个配置了 `online_config` 的模型，并发发起一次 OpenAI 兼容的
+    /// chat completion 请求（`chat_system_prompt` 作为 system 消息，`translation_prompt` 中的
+    /// `{context}` 替换为 `model_test_input` 作为 user 消息），用于在不启动后端的情况下
+    /// 快速验证 API key、base URL 与提示词模板是否可用
+    fn run_model_test(&mut self, ctx: egui::Context) {
+        if self.model_test_input.trim().is_empty() {
+            self.status_message = "Model Test: input is empty".to_string();
+            return;
+        }
+
+        let models: Vec<Model> = self
+            .config
+            .models
+            .iter()
+            .filter(|m| m.online_config.is_some())
+            .cloned()
+            .collect();
+        if models.is_empty() {
+            self.status_message = "Model Test: no models have an online_config".to_string();
+            return;
+        }
+
+        let sample_text = self.model_test_input.clone();
+        let proxy = self.config.proxy.clone();
+
+        self.model_test_expected = models.len();
+        {
+            let mut results = self.model_test_results.lock().unwrap();
+            results.clear();
+        }
+        ctx.request_repaint();
+
+        let results_arc = Arc::clone(&self.model_test_results);
+        let ctx_clone = ctx.clone();
+
+        thread::spawn(move || {
+            use std::sync::mpsc;
+            let agent = Self::build_http_agent(&proxy, false, std::time::Duration::from_secs(60));
+
+            let (tx, rx) = mpsc::channel();
+
+            for model in models {
+                let tx = tx.clone();
+                let agent = agent.clone();
+                let sample_text = sample_text.clone();
+                thread::spawn(move || {
+                    // `online_config` 已在上面过滤过，这里必然存在
+                    let online = model.online_config.clone().unwrap();
+                    let user_prompt = model
+                        .prompt_templates
+                        .translation_prompt
+                        .replace("{context}", &sample_text);
+                    let url = format!("{}/chat/completions", online.api_base.trim_end_matches('/'));
+                    let payload = serde_json::json!({
+                        "model": online.model_name,
+                        "messages": [
+                            {"role": "system", "content": model.prompt_templates.chat_system_prompt},
+                            {"role": "user", "content": user_prompt},
+                        ],
+                    });
+
+                    let start = std::time::Instant::now();
+                    let result = match agent
+                        .post(&url)
+                        .set("Authorization", &format!("Bearer {}", online.api_key))
+                        .send_json(payload)
+                    {
+                        Ok(response) => match response.into_json::<serde_json::Value>() {
+                            Ok(body) => {
+                                let text = body["choices"][0]["message"]["content"]
+                                    .as_str()
+                                    .unwrap_or("")
+                                    .to_string();
+                                ModelTestResult {
+                                    model_path: model.model_path.clone(),
+                                    model_name: online.model_name.clone(),
+                                    response_text: text,
+                                    latency_ms: start.elapsed().as_millis(),
+                                    error: None,
+                                }
+                            }
+                            Err(e) => ModelTestResult {
+                                model_path: model.model_path.clone(),
+                                model_name: online.model_name.clone(),
+                                response_text: String::new(),
+                                latency_ms: start.elapsed().as_millis(),
+                                error: Some(format!("Invalid response: {}", e)),
+                            },
+                        },
+                        Err(e) => ModelTestResult {
+                            model_path: model.model_path.clone(),
+                            model_name: online.model_name.clone(),
+                            response_text: String::new(),
+                            latency_ms: start.elapsed().as_millis(),
+                            error: Some(e.to_string()),
+                        },
+                    };
+                    let _ = tx.send(result);
+                });
+            }
+
+            drop(tx);
+
+            for result in rx {
+                {
+                    let mut results = results_arc.lock().unwrap();
+                    results.push(result);
+                }
+                ctx_clone.request_repaint();
+            }
+        });
+    }
+
+    fn show_model_test_ui(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        ui.heading(self.tr("tab.model_test"));
+        ui.add_space(10.0);
+        ui.label(
+            "Sends a sample source text to every model with an online_config, in parallel, \
+             using each model's own prompt templates — a quick way to sanity-check API keys, \
+             base URLs, and prompts before starting the backend.",
+        );
+        ui.add_space(6.0);
+
+        ui.label("Sample Source Text:");
+        ui.add(
+            egui::TextEdit::multiline(&mut self.model_test_input)
+                .desired_rows(4)
+                .desired_width(f32::INFINITY),
+        );
+        ui.add_space(6.0);
+
+        let running = {
+            let results = self.model_test_results.lock().unwrap();
+            self.model_test_expected > 0 && results.len() < self.model_test_expected
+        };
+        ui.add_enabled_ui(!running, |ui| {
+            if ui.button("Run Model Test").clicked() {
+                self.run_model_test(ctx.clone());
+            }
+        });
+        if running {
+            ui.label(format!(
+                "Running... {}/{} models finished",
+                self.model_test_results.lock().unwrap().len(),
+                self.model_test_expected
+            ));
+        }
+
+        ui.add_space(10.0);
+        ui.separator();
+        ui.add_space(10.0);
+
+        let results = self.model_test_results.lock().unwrap();
+        egui::Grid::new("model_test_grid")
+            .num_columns(4)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label(egui::RichText::new("Model").strong());
+                ui.label(egui::RichText::new("Response").strong());
+                ui.label(egui::RichText::new("Latency").strong());
+                ui.label(egui::RichText::new("Error").strong());
+                ui.end_row();
+
+                for result in results.iter() {
+                    ui.label(format!("{} ({})", result.model_path, result.model_name));
+                    ui.label(&result.response_text);
+                    ui.label(format!("{} ms", result.latency_ms));
+                    if let Some(err) = &result.error {
+                        ui.label(egui::RichText::new(err).color(egui::Color32::RED));
+                    } else {
+                        ui.label("");
+                    }
+                    ui.end_row();
+                }
+            });
+    }
 }
 
 fn setup_fonts(ctx: &egui::Context) {
@@ -1634,6 +4371,14 @@ impl Config {
                     dense_subtitles: true,
                 },
             ],
+            notifications_enabled: default_notifications_enabled(),
+            services: default_services(),
+            log_dir: default_log_dir(),
+            log_max_size_mb: default_log_max_size_mb(),
+            log_max_backups: default_log_max_backups(),
+            theme: ThemeVariant::default(),
+            ui_language: Language::default(),
+            proxy: ProxyConfig::default(),
         }
     }
 }
@@ -1653,6 +4398,14 @@ impl Default for Config {
             media_directories: vec![],
             models: vec![],
             transcriber_models: vec![],
+            notifications_enabled: default_notifications_enabled(),
+            services: default_services(),
+            log_dir: default_log_dir(),
+            log_max_size_mb: default_log_max_size_mb(),
+            log_max_backups: default_log_max_backups(),
+            theme: ThemeVariant::default(),
+            ui_language: Language::default(),
+            proxy: ProxyConfig::default(),
         }
     }
 }
@@ -1700,18 +4453,85 @@ fn main() -> Result<(), eframe::Error> {
 
 impl Drop for MyApp {
     fn drop(&mut self) {
-        // 停止 Node 服务器
-        MyApp::stop_process(
-            Arc::clone(&self.node_server),
-            "Node",
-            &self.log_sender,
+        // 逐个停止所有受管理服务（应用退出属于预期行为，不弹通知）
+        for (service, state) in self.config.services.iter().zip(self.services.iter()) {
+            MyApp::stop_process(
+                Arc::clone(state),
+                &service.display_name,
+                &self.log_sender,
+                false,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOLD_FONT_SIZE: f32 = 12.0;
+    const PLAIN_FONT_SIZE: f32 = 11.0;
+
+    /// 无转义序列时应走快速路径，整行文本落入单个格式段
+    #[test]
+    fn test_plain_text_has_single_section() {
+        let job = MyApp::parse_ansi_to_layout_job("hello world", ThemeVariant::Dark);
+        assert_eq!(job.sections.len(), 1);
+        assert_eq!(job.sections[0].format.font_id.size, PLAIN_FONT_SIZE);
+    }
+
+    /// 256 色调色板中 16-231 段是 6x6x6 色立方体；code 196 应解析为纯红 (255,0,0)
+    #[test]
+    fn test_256_color_cube() {
+        let job = MyApp::parse_ansi_to_layout_job("\x1b[38;5;196mred", ThemeVariant::Dark);
+        assert_eq!(job.sections.last().unwrap().format.color, egui::Color32::from_rgb(255, 0, 0));
+    }
+
+    /// 256 色调色板中 232-255 段是灰阶渐变；code 244 应解析为灰度 8 + (244-232)*10 = 128
+    #[test]
+    fn test_256_color_grayscale_ramp() {
+        let job = MyApp::parse_ansi_to_layout_job("\x1b[38;5;244mgray", ThemeVariant::Dark);
+        assert_eq!(
+            job.sections.last().unwrap().format.color,
+            egui::Color32::from_gray(128)
         );
+    }
 
-        // 停止 Python 后端
-        MyApp::stop_process(
-            Arc::clone(&self.python_server),
-            "Python",
-            &self.log_sender,
+    /// 真彩色 38;2;r;g;b 应直接解析为对应 RGB，不经过任何调色板映射
+    #[test]
+    fn test_truecolor() {
+        let job = MyApp::parse_ansi_to_layout_job("\x1b[38;2;10;20;30mtruecolor", ThemeVariant::Dark);
+        assert_eq!(
+            job.sections.last().unwrap().format.color,
+            egui::Color32::from_rgb(10, 20, 30)
         );
     }
+
+    /// 一次 SGR 序列中组合多个参数（粗体 + 下划线 + 主题红）应同时生效
+    #[test]
+    fn test_combined_sgr_params() {
+        let job = MyApp::parse_ansi_to_layout_job("\x1b[1;4;31mHello", ThemeVariant::Dark);
+        let format = &job.sections.last().unwrap().format;
+        assert_eq!(format.font_id.size, BOLD_FONT_SIZE);
+        assert_ne!(format.underline, egui::Stroke::NONE);
+        assert_eq!(format.color, ThemeVariant::Dark.palette().error);
+    }
+
+    /// 裸重置 `\x1b[m`（空参数列表）应等价于 code 0，清空之前累积的所有属性
+    #[test]
+    fn test_bare_reset_clears_attributes() {
+        let job = MyApp::parse_ansi_to_layout_job("\x1b[1;31mred bold\x1b[mplain", ThemeVariant::Dark);
+        let plain = &job.sections.last().unwrap().format;
+        assert_eq!(plain.font_id.size, PLAIN_FONT_SIZE);
+        assert_eq!(plain.color, ThemeVariant::Dark.visuals().widgets.noninteractive.fg_stroke.color);
+    }
+
+    /// 属性关闭码（22/23/24）应只清除对应属性，不影响同一状态下的其他属性
+    #[test]
+    fn test_attribute_toggle_off() {
+        let job = MyApp::parse_ansi_to_layout_job("\x1b[1;4mboth\x1b[22mbold off", ThemeVariant::Dark);
+        let format = &job.sections.last().unwrap().format;
+        assert_eq!(format.font_id.size, PLAIN_FONT_SIZE);
+        assert_ne!(format.underline, egui::Stroke::NONE);
+    }
 }
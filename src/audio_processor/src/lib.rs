@@ -3,7 +3,9 @@
 //! 实现了完整的 K 权重滤波链路和滑动窗口能量积分：
 //!  - 瞬时响度 (Momentary LUFS): 400ms 滑动窗口
 //!  - 短期响度 (Short-term LUFS): 3000ms 滑动窗口
+//!  - 节目响度 (Integrated LUFS): 全门限算法（绝对门限 + 相对门限）
 //!  - 采样峰值 (Sample Peak)
+//!  - True Peak (4× 过采样估计)
 //!
 //! 滤波器系数计算参考 ITU-R BS.1770-4 附录 1，
 //! 使用双线性变换 (bilinear transform) 在任意采样率下推导精确系数。
@@ -52,6 +54,13 @@ impl Biquad {
         self.z2 = self.b2 * x - self.a2 * y;
         y
     }
+
+    /// 清空延迟线状态（保留系数）
+    #[inline(always)]
+    fn reset(&mut self) {
+        self.z1 = 0.0;
+        self.z2 = 0.0;
+    }
 }
 
 // ============================================================
@@ -190,6 +199,121 @@ impl RingBuffer {
     }
 }
 
+// ============================================================
+// True Peak 检测（4× 过采样，ITU-R BS.1770-4 附录2）
+// ============================================================
+
+/// 过采样倍数（4×，符合 BS.1770-4 附录2 推荐的最低过采样率）
+const TP_OVERSAMPLE: usize = 4;
+/// 多相子滤波器每相的抽头数（总抽头数 48 / 4 相 = 12）
+const TP_TAPS_PER_PHASE: usize = 12;
+/// 原型低通 FIR 的总抽头数（48-tap Kaiser 窗 sinc）
+const TP_TOTAL_TAPS: usize = TP_OVERSAMPLE * TP_TAPS_PER_PHASE;
+/// Kaiser 窗 β 参数（较大的 β 换取更强的阻带衰减）
+const TP_KAISER_BETA: f64 = 8.0;
+
+/// 修正的零阶第一类贝塞尔函数 I0（用于 Kaiser 窗系数）
+fn bessel_i0(x: f64) -> f64 {
+    let mut sum = 1.0_f64;
+    let mut term = 1.0_f64;
+    let half_x = x / 2.0;
+    // 级数展开到足够的项数即可收敛到双精度误差范围内
+    for k in 1..25 {
+        term *= (half_x * half_x) / (k as f64 * k as f64);
+        sum += term;
+    }
+    sum
+}
+
+/// 生成 4× 过采样用的 48-tap Kaiser 窗 windowed-sinc 低通原型滤波器，
+/// 截止频率设在原始信号的奈奎斯特频率（即过采样后 Fs 的 1/8）。
+///
+/// 返回按相位拆分的多相子滤波器组：`phase[p][k] = h[4k + p]`，
+/// 这样每个输出采样只需 `TP_TAPS_PER_PHASE` 次乘加，而非对整段零填充序列做完整卷积。
+fn build_true_peak_polyphase() -> [[f64; TP_TAPS_PER_PHASE]; TP_OVERSAMPLE] {
+    use std::f64::consts::PI;
+
+    let m = (TP_TOTAL_TAPS - 1) as f64; // 47
+    let center = m / 2.0; // 23.5
+    let fc_norm = 1.0 / (2.0 * TP_OVERSAMPLE as f64); // 过采样后 Fs 的归一化截止频率 = 0.125
+
+    let mut h = [0.0_f64; TP_TOTAL_TAPS];
+    let i0_beta = bessel_i0(TP_KAISER_BETA);
+
+    for (n, slot) in h.iter_mut().enumerate() {
+        let x = n as f64 - center;
+
+        // 理想低通的 sinc 响应
+        let sinc = if x.abs() < 1e-9 {
+            2.0 * fc_norm
+        } else {
+            (2.0 * PI * fc_norm * x).sin() / (PI * x)
+        };
+
+        // Kaiser 窗
+        let ratio = x / center;
+        let window_arg = TP_KAISER_BETA * (1.0 - ratio * ratio).max(0.0).sqrt();
+        let window = bessel_i0(window_arg) / i0_beta;
+
+        *slot = sinc * window;
+    }
+
+    let mut phases = [[0.0_f64; TP_TAPS_PER_PHASE]; TP_OVERSAMPLE];
+    for p in 0..TP_OVERSAMPLE {
+        for k in 0..TP_TAPS_PER_PHASE {
+            phases[p][k] = h[TP_OVERSAMPLE * k + p];
+        }
+    }
+    phases
+}
+
+/// 单声道 True Peak 检测器：对输入信号做 4× 多相插值，在插值点上取幅值的滑动最大值
+struct TruePeakFilter {
+    /// 最近 `TP_TAPS_PER_PHASE` 个输入采样的历史（`history[0]` 为最新）
+    history: [f64; TP_TAPS_PER_PHASE],
+    /// 多相子滤波器组（各实例共享同一组系数，按相位预先拆分）
+    phases: [[f64; TP_TAPS_PER_PHASE]; TP_OVERSAMPLE],
+    /// 线性幅度下的 True Peak 保持值
+    peak: f32,
+}
+
+impl TruePeakFilter {
+    fn new() -> Self {
+        Self {
+            history: [0.0; TP_TAPS_PER_PHASE],
+            phases: build_true_peak_polyphase(),
+            peak: 0.0,
+        }
+    }
+
+    /// 推入一个新采样，更新 4 个插值相位并刷新 True Peak 保持值
+    #[inline(always)]
+    fn push(&mut self, x: f64) {
+        // 历史右移一位，x 成为最新样本
+        for i in (1..TP_TAPS_PER_PHASE).rev() {
+            self.history[i] = self.history[i - 1];
+        }
+        self.history[0] = x;
+
+        for p in 0..TP_OVERSAMPLE {
+            let mut acc = 0.0_f64;
+            for k in 0..TP_TAPS_PER_PHASE {
+                acc += self.phases[p][k] * self.history[k];
+            }
+            // 补偿零填充插值带来的能量损失（乘以过采样倍数）
+            let interpolated = (acc * TP_OVERSAMPLE as f64).abs() as f32;
+            if interpolated > self.peak {
+                self.peak = interpolated;
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        self.history = [0.0; TP_TAPS_PER_PHASE];
+        self.peak = 0.0;
+    }
+}
+
 // ============================================================
 // LUFS 响度计算辅助函数
 // ============================================================
@@ -197,6 +321,11 @@ impl RingBuffer {
 /// LUFS 偏移常数（来自 ITU-R BS.1770 定义）
 const LUFS_OFFSET: f64 = -0.691_f64;
 
+/// Integrated Loudness 门限：绝对门限（ITU-R BS.1770-4 §2.3）
+const ABSOLUTE_GATE_LUFS: f64 = -70.0;
+/// Integrated Loudness 门限：相对门限相对于未门限平均响度的偏移量
+const RELATIVE_GATE_OFFSET_LU: f64 = -10.0;
+
 /// 均方功率 → LUFS 响度值
 ///
 /// L = -0.691 + 10 · log₁₀(∑ Wᵢ · Mᵢ²)
@@ -245,13 +374,24 @@ pub struct LufsMeter {
     peak_l: f32,
     peak_r: f32,
 
+    // True Peak 检测（4× 过采样，比采样峰值更准确地反映 D/A 重建后的真实峰值）
+    true_peak_l: TruePeakFilter,
+    true_peak_r: TruePeakFilter,
+
     // 输出节流控制：累积足够样本才输出一次结果（约 20 Hz 更新率）
     sample_count: u32,
     output_interval: u32,
 
+    // Integrated Loudness 门限计算块：每 100ms 采样一次 400ms 窗口均方功率（75% 重叠）
+    gating_sample_count: u32,
+    gating_block_interval: u32,
+    // 通过绝对门限（-70 LUFS）保留下来的门限块均方功率，二次遍历时再应用相对门限
+    integrated_blocks: Vec<f64>,
+
     // 缓存的最新计算结果
     cached_momentary: f32,
     cached_short_term: f32,
+    cached_integrated: f32,
 }
 
 #[wasm_bindgen]
@@ -271,6 +411,9 @@ impl LufsMeter {
         // 约 20 Hz 更新率（每 50ms 输出一次）
         let output_interval = (fs * 0.05).round() as u32;
 
+        // 门限块节奏：每 100ms 采样一次（与 400ms 窗口相比为 75% 重叠）
+        let gating_block_interval = (fs * 0.1).round() as u32;
+
         LufsMeter {
             filter_l: KWeightFilter::new(fs),
             filter_r: KWeightFilter::new(fs),
@@ -280,10 +423,16 @@ impl LufsMeter {
             short_r: RingBuffer::new(short_term_samples),
             peak_l: 0.0,
             peak_r: 0.0,
+            true_peak_l: TruePeakFilter::new(),
+            true_peak_r: TruePeakFilter::new(),
             sample_count: 0,
             output_interval,
+            gating_sample_count: 0,
+            gating_block_interval,
+            integrated_blocks: Vec::new(),
             cached_momentary: -144.0,
             cached_short_term: -144.0,
+            cached_integrated: -144.0,
         }
     }
 
@@ -316,7 +465,7 @@ impl LufsMeter {
             self.short_l.push(sq_l);
             self.short_r.push(sq_r);
 
-            // 更新采样峰值（取绝对值最大，未经过采样 —— 近似 True Peak）
+            // 更新采样峰值（取绝对值最大，未经过采样）
             let al = l.abs() as f32;
             let ar = r.abs() as f32;
             if al > self.peak_l {
@@ -325,9 +474,24 @@ impl LufsMeter {
             if ar > self.peak_r {
                 self.peak_r = ar;
             }
+
+            // 4× 过采样插值，跟踪 True Peak
+            self.true_peak_l.push(l);
+            self.true_peak_r.push(r);
         }
 
         self.sample_count += n as u32;
+        self.gating_sample_count += n as u32;
+
+        // 门限块：每 100ms 从当前 400ms 窗口采样一次均方功率（窗口本身已滑动维护，
+        // 因此相邻块天然具有 75% 重叠），只保留通过绝对门限的块供积分响度二次遍历使用
+        while self.gating_sample_count >= self.gating_block_interval {
+            self.gating_sample_count -= self.gating_block_interval;
+            let block_ms = (self.moment_l.mean() + self.moment_r.mean()) * 0.5;
+            if power_to_lufs(block_ms) as f64 >= ABSOLUTE_GATE_LUFS {
+                self.integrated_blocks.push(block_ms);
+            }
+        }
 
         if self.sample_count >= self.output_interval {
             self.sample_count = 0;
@@ -339,6 +503,7 @@ impl LufsMeter {
 
             self.cached_momentary = power_to_lufs(momentary_ms);
             self.cached_short_term = power_to_lufs(short_term_ms);
+            self.cached_integrated = self.compute_integrated_lufs();
 
             return true;
         }
@@ -346,6 +511,32 @@ impl LufsMeter {
         false
     }
 
+    /// 对保留的门限块做二次遍历：计算其平均响度得到相对门限（均值 − 10 LU），
+    /// 再在相对门限之上的块中求平均功率，得到最终 Integrated Loudness
+    fn compute_integrated_lufs(&self) -> f32 {
+        if self.integrated_blocks.is_empty() {
+            return -144.0;
+        }
+
+        let mean_power: f64 =
+            self.integrated_blocks.iter().sum::<f64>() / self.integrated_blocks.len() as f64;
+        let relative_gate = power_to_lufs(mean_power) as f64 + RELATIVE_GATE_OFFSET_LU;
+
+        let mut gated_sum = 0.0_f64;
+        let mut gated_count = 0usize;
+        for &power in self.integrated_blocks.iter() {
+            if power_to_lufs(power) as f64 >= relative_gate {
+                gated_sum += power;
+                gated_count += 1;
+            }
+        }
+
+        if gated_count == 0 {
+            return -144.0;
+        }
+        power_to_lufs(gated_sum / gated_count as f64)
+    }
+
     /// 瞬时响度（Momentary LUFS，400ms 窗口）
     #[wasm_bindgen(getter)]
     pub fn momentary_lufs(&self) -> f32 {
@@ -360,7 +551,8 @@ impl LufsMeter {
 
     /// 左声道采样峰值（dBFS）
     ///
-    /// 注意：这是采样峰值而非过采样 True Peak，实际 TP 会略高（约 +0.5 到 +2 dBFS）
+    /// 注意：这是采样峰值而非过采样 True Peak，实际 TP 会略高（约 +0.5 到 +2 dBFS），
+    /// 如需更准确的峰值估计请使用 `true_peak_l_db`
     #[wasm_bindgen(getter)]
     pub fn peak_l_db(&self) -> f32 {
         if self.peak_l < 1.0e-10 {
@@ -392,10 +584,44 @@ impl LufsMeter {
         self.peak_r
     }
 
+    /// Integrated Loudness（Program Loudness，BS.1770-4 全门限算法）
+    ///
+    /// 这是实际用于响度归一化的数值（例如 Spotify/YouTube 目标 -14 LUFS），
+    /// 与瞬时/短期响度不同，它代表整段节目材料的平均响度
+    #[wasm_bindgen(getter)]
+    pub fn integrated_lufs(&self) -> f32 {
+        self.cached_integrated
+    }
+
+    /// 左声道 True Peak（dBFS，4× 过采样估计）
+    ///
+    /// 比 `peak_l_db` 更接近 D/A 重建后的真实峰值，能捕捉到采样点之间
+    /// 被采样峰值遗漏的瞬态过冲（通常比采样峰值高 +0.5 ~ +2 dBFS）
+    #[wasm_bindgen(getter)]
+    pub fn true_peak_l_db(&self) -> f32 {
+        if self.true_peak_l.peak < 1.0e-10 {
+            -144.0
+        } else {
+            20.0 * self.true_peak_l.peak.log10()
+        }
+    }
+
+    /// 右声道 True Peak（dBFS，4× 过采样估计）
+    #[wasm_bindgen(getter)]
+    pub fn true_peak_r_db(&self) -> f32 {
+        if self.true_peak_r.peak < 1.0e-10 {
+            -144.0
+        } else {
+            20.0 * self.true_peak_r.peak.log10()
+        }
+    }
+
     /// 重置峰值保持
     pub fn reset_peak(&mut self) {
         self.peak_l = 0.0;
         self.peak_r = 0.0;
+        self.true_peak_l.peak = 0.0;
+        self.true_peak_r.peak = 0.0;
     }
 
     /// 重置所有状态（包括滤波器和缓冲区）
@@ -428,9 +654,14 @@ impl LufsMeter {
         // 重置峰值和缓存
         self.peak_l = 0.0;
         self.peak_r = 0.0;
+        self.true_peak_l.reset();
+        self.true_peak_r.reset();
         self.sample_count = 0;
+        self.gating_sample_count = 0;
+        self.integrated_blocks.clear();
         self.cached_momentary = -144.0;
         self.cached_short_term = -144.0;
+        self.cached_integrated = -144.0;
     }
 
     /// 获取瞬时窗口的填充进度（0.0 ~ 1.0）
@@ -446,6 +677,571 @@ impl LufsMeter {
     }
 }
 
+// ============================================================
+// 多段参数均衡器 (Equalizer)
+// ============================================================
+
+/// RBJ "Audio EQ Cookbook" 峰值 (peaking) 滤波器系数
+///
+/// A = 10^(G/40)，ω0 = 2π·f0/fs，α = sin(ω0)/(2Q)
+fn rbj_peaking_coeffs(fs: f64, f0: f64, gain_db: f64, q: f64) -> (f64, f64, f64, f64, f64) {
+    let a = 10_f64.powf(gain_db / 40.0);
+    let w0 = 2.0 * std::f64::consts::PI * f0 / fs;
+    let alpha = w0.sin() / (2.0 * q);
+    let cos_w0 = w0.cos();
+
+    let a0 = 1.0 + alpha / a;
+    let b0 = (1.0 + alpha * a) / a0;
+    let b1 = (-2.0 * cos_w0) / a0;
+    let b2 = (1.0 - alpha * a) / a0;
+    let a1 = (-2.0 * cos_w0) / a0;
+    let a2 = (1.0 - alpha / a) / a0;
+    (b0, b1, b2, a1, a2)
+}
+
+/// RBJ "Audio EQ Cookbook" 低搁架 (low-shelf) 滤波器系数
+fn rbj_low_shelf_coeffs(fs: f64, f0: f64, gain_db: f64, q: f64) -> (f64, f64, f64, f64, f64) {
+    let a = 10_f64.powf(gain_db / 40.0);
+    let w0 = 2.0 * std::f64::consts::PI * f0 / fs;
+    let cos_w0 = w0.cos();
+    let alpha = w0.sin() / (2.0 * q);
+    let two_sqrt_a_alpha = 2.0 * a.sqrt() * alpha;
+
+    let a0 = (a + 1.0) + (a - 1.0) * cos_w0 + two_sqrt_a_alpha;
+    let b0 = (a * ((a + 1.0) - (a - 1.0) * cos_w0 + two_sqrt_a_alpha)) / a0;
+    let b1 = (2.0 * a * ((a - 1.0) - (a + 1.0) * cos_w0)) / a0;
+    let b2 = (a * ((a + 1.0) - (a - 1.0) * cos_w0 - two_sqrt_a_alpha)) / a0;
+    let a1 = (-2.0 * ((a - 1.0) + (a + 1.0) * cos_w0)) / a0;
+    let a2 = ((a + 1.0) + (a - 1.0) * cos_w0 - two_sqrt_a_alpha) / a0;
+    (b0, b1, b2, a1, a2)
+}
+
+/// RBJ "Audio EQ Cookbook" 高搁架 (high-shelf) 滤波器系数
+fn rbj_high_shelf_coeffs(fs: f64, f0: f64, gain_db: f64, q: f64) -> (f64, f64, f64, f64, f64) {
+    let a = 10_f64.powf(gain_db / 40.0);
+    let w0 = 2.0 * std::f64::consts::PI * f0 / fs;
+    let cos_w0 = w0.cos();
+    let alpha = w0.sin() / (2.0 * q);
+    let two_sqrt_a_alpha = 2.0 * a.sqrt() * alpha;
+
+    let a0 = (a + 1.0) - (a - 1.0) * cos_w0 + two_sqrt_a_alpha;
+    let b0 = (a * ((a + 1.0) + (a - 1.0) * cos_w0 + two_sqrt_a_alpha)) / a0;
+    let b1 = (-2.0 * a * ((a - 1.0) + (a + 1.0) * cos_w0)) / a0;
+    let b2 = (a * ((a + 1.0) + (a - 1.0) * cos_w0 - two_sqrt_a_alpha)) / a0;
+    let a1 = (2.0 * ((a - 1.0) - (a + 1.0) * cos_w0)) / a0;
+    let a2 = ((a + 1.0) - (a - 1.0) * cos_w0 - two_sqrt_a_alpha) / a0;
+    (b0, b1, b2, a1, a2)
+}
+
+/// 均衡段类型
+#[derive(Clone, Copy, PartialEq)]
+enum BandType {
+    Peaking,
+    LowShelf,
+    HighShelf,
+}
+
+impl BandType {
+    fn from_str(s: &str) -> BandType {
+        match s {
+            "lowshelf" | "low_shelf" => BandType::LowShelf,
+            "highshelf" | "high_shelf" => BandType::HighShelf,
+            _ => BandType::Peaking,
+        }
+    }
+
+    fn coeffs(self, fs: f64, freq: f64, gain_db: f64, q: f64) -> (f64, f64, f64, f64, f64) {
+        match self {
+            BandType::Peaking => rbj_peaking_coeffs(fs, freq, gain_db, q),
+            BandType::LowShelf => rbj_low_shelf_coeffs(fs, freq, gain_db, q),
+            BandType::HighShelf => rbj_high_shelf_coeffs(fs, freq, gain_db, q),
+        }
+    }
+}
+
+/// 单个均衡段：左右声道各自独立的延迟线状态，共享同一组系数
+struct EqBand {
+    band_type: BandType,
+    freq: f64,
+    gain_db: f64,
+    q: f64,
+    left: Biquad,
+    right: Biquad,
+}
+
+impl EqBand {
+    /// 构造一个中性（0dB 增益）的峰值段，作为默认占位
+    fn identity(fs: f64) -> Self {
+        let mut band = EqBand {
+            band_type: BandType::Peaking,
+            freq: 1000.0,
+            gain_db: 0.0,
+            q: std::f64::consts::FRAC_1_SQRT_2,
+            left: Biquad::new(1.0, 0.0, 0.0, 0.0, 0.0),
+            right: Biquad::new(1.0, 0.0, 0.0, 0.0, 0.0),
+        };
+        band.rebuild(fs);
+        band
+    }
+
+    /// 按当前段参数重新计算系数（会重置延迟线，与瞬时调参一致）
+    fn rebuild(&mut self, fs: f64) {
+        let (b0, b1, b2, a1, a2) = self.band_type.coeffs(fs, self.freq, self.gain_db, self.q);
+        self.left = Biquad::new(b0, b1, b2, a1, a2);
+        self.right = Biquad::new(b0, b1, b2, a1, a2);
+    }
+
+    fn reset(&mut self) {
+        self.left.reset();
+        self.right.reset();
+    }
+}
+
+/// 软削波限幅：超过阈值后用 tanh 平滑压缩，避免级联 IIR 相移叠加导致的爆音
+#[inline(always)]
+fn soft_clip(x: f64, threshold: f64) -> f64 {
+    let ax = x.abs();
+    if ax <= threshold {
+        return x;
+    }
+    let sign = x.signum();
+    let excess = (ax - threshold) / (1.0 - threshold);
+    sign * (threshold + (1.0 - threshold) * excess.tanh())
+}
+
+/// 基于现有 `Biquad` 的多段参数均衡器（WASM 公共 API）
+///
+/// 每段可独立配置为峰值 (peaking)、低搁架 (lowshelf) 或高搁架 (highshelf)，
+/// 系数使用 RBJ "Audio EQ Cookbook" 公式计算。级联多段后叠加增益可能推高峰值，
+/// 因此提供可选的软削波限幅级。
+#[wasm_bindgen]
+pub struct Equalizer {
+    sample_rate: f64,
+    bands: Vec<EqBand>,
+    limiter_enabled: bool,
+    limiter_threshold: f64,
+}
+
+#[wasm_bindgen]
+impl Equalizer {
+    /// 创建均衡器
+    ///
+    /// # 参数
+    /// - `sample_rate`: 采样率（Hz）
+    /// - `band_count`: 均衡段数量，初始均为 0dB 增益的中性峰值段
+    #[wasm_bindgen(constructor)]
+    pub fn new(sample_rate: f32, band_count: usize) -> Equalizer {
+        let fs = sample_rate as f64;
+        Equalizer {
+            sample_rate: fs,
+            bands: (0..band_count).map(|_| EqBand::identity(fs)).collect(),
+            limiter_enabled: false,
+            limiter_threshold: 0.95,
+        }
+    }
+
+    /// 配置指定段（越界时忽略）
+    ///
+    /// # 参数
+    /// - `index`: 段序号
+    /// - `band_type`: `"peaking"` / `"lowshelf"` / `"highshelf"`
+    /// - `freq`: 中心（或拐点）频率，Hz
+    /// - `gain_db`: 增益，dB
+    /// - `q`: 品质因数
+    pub fn set_band(&mut self, index: usize, band_type: &str, freq: f32, gain_db: f32, q: f32) {
+        if let Some(band) = self.bands.get_mut(index) {
+            band.band_type = BandType::from_str(band_type);
+            band.freq = freq as f64;
+            band.gain_db = gain_db as f64;
+            band.q = q as f64;
+            band.rebuild(self.sample_rate);
+        }
+    }
+
+    /// 启用/禁用输出端软削波限幅
+    pub fn set_limiter_enabled(&mut self, enabled: bool) {
+        self.limiter_enabled = enabled;
+    }
+
+    /// 立体声逐块处理（原地修改）
+    pub fn process_block(&mut self, left: &mut [f32], right: &mut [f32]) {
+        let n = left.len().min(right.len());
+        let threshold = self.limiter_threshold;
+        for i in 0..n {
+            let mut l = left[i] as f64;
+            let mut r = right[i] as f64;
+            for band in self.bands.iter_mut() {
+                l = band.left.process(l);
+                r = band.right.process(r);
+            }
+            if self.limiter_enabled {
+                l = soft_clip(l, threshold);
+                r = soft_clip(r, threshold);
+            }
+            left[i] = l as f32;
+            right[i] = r as f32;
+        }
+    }
+
+    /// 重置所有段的延迟线状态（保留已配置的系数）
+    pub fn reset(&mut self) {
+        for band in self.bands.iter_mut() {
+            band.reset();
+        }
+    }
+}
+
+// ============================================================
+// 响度归一化器 (LoudnessNormalizer)
+// ============================================================
+
+/// 限幅器软膝区起始阈值（线性幅度），超过此值切换为压缩特性而非线性直通
+const LIMITER_KNEE_THRESHOLD: f64 = 0.891_250_938; // -1.0 dBFS
+/// 软膝区压缩比（近似 WebRTC AGC 参考实现中 kCompRatio≈3 的区域）
+const LIMITER_COMP_RATIO: f64 = 3.0;
+
+#[inline(always)]
+fn db_to_linear(db: f64) -> f64 {
+    10_f64.powf(db / 20.0)
+}
+
+#[inline(always)]
+fn linear_to_db(linear: f64) -> f64 {
+    if linear < 1.0e-10 {
+        -200.0
+    } else {
+        20.0 * linear.log10()
+    }
+}
+
+/// 对单个样本施加软膝限幅：超过 `LIMITER_KNEE_THRESHOLD` 后按 `LIMITER_COMP_RATIO`
+/// 压缩超出部分，避免增益调整把瞬态推过满量程时产生硬削波
+#[inline(always)]
+fn soft_knee_limit(x: f64) -> (f64, bool) {
+    let ax = x.abs();
+    if ax <= LIMITER_KNEE_THRESHOLD {
+        return (x, false);
+    }
+    let sign = x.signum();
+    let over_db = linear_to_db(ax) - linear_to_db(LIMITER_KNEE_THRESHOLD);
+    let compressed_db = linear_to_db(LIMITER_KNEE_THRESHOLD) + over_db / LIMITER_COMP_RATIO;
+    (sign * db_to_linear(compressed_db), true)
+}
+
+/// 基于 K 权重响度测量的流式响度归一化器
+///
+/// 内部复用 `LufsMeter` 持续测量短期响度，据此计算出期望的静态增益
+/// （目标响度 − 测得响度），再以独立的 attack/release 时间常数对线性增益
+/// 做逐样本一阶平滑，避免增益突变造成的"泵浦"听感。当平滑后的信号样本
+/// 逼近 0 dBFS 时切换为软膝压缩特性，防止削波。
+#[wasm_bindgen]
+pub struct LoudnessNormalizer {
+    meter: LufsMeter,
+    target_lufs: f64,
+    max_gain_db: f64,
+    attack_coeff: f64,
+    release_coeff: f64,
+    current_gain: f64,
+    limiter_active: bool,
+}
+
+#[wasm_bindgen]
+impl LoudnessNormalizer {
+    /// 创建归一化器
+    ///
+    /// # 参数
+    /// - `sample_rate`: 采样率（Hz）
+    /// - `target_lufs`: 目标响度（流媒体常用 -14 LUFS，广播常用 -23 LUFS）
+    #[wasm_bindgen(constructor)]
+    pub fn new(sample_rate: f32, target_lufs: f32) -> LoudnessNormalizer {
+        let fs = sample_rate as f64;
+        LoudnessNormalizer {
+            meter: LufsMeter::new(sample_rate),
+            target_lufs: target_lufs as f64,
+            max_gain_db: 24.0,
+            // 攻击（压低过响信号）约 50ms，释放（恢复增益）约 2000ms，
+            // 时间常数越短跟随越快——攻击需快以避免瞬态溢出，释放需慢以避免泵浦感
+            attack_coeff: (-1.0 / (0.050 * fs)).exp(),
+            release_coeff: (-1.0 / (2.000 * fs)).exp(),
+            current_gain: 1.0,
+            limiter_active: false,
+        }
+    }
+
+    /// 配置增益变化的最大幅度（dB），防止静音或测量噪声导致增益失控
+    pub fn set_max_gain_db(&mut self, max_gain_db: f32) {
+        self.max_gain_db = max_gain_db as f64;
+    }
+
+    /// 设置目标响度（LUFS）
+    pub fn set_target_lufs(&mut self, target_lufs: f32) {
+        self.target_lufs = target_lufs as f64;
+    }
+
+    /// 立体声逐块测量并原地施加增益
+    pub fn process_block(&mut self, left: &mut [f32], right: &mut [f32]) {
+        self.meter.process_block(left, right);
+
+        let measured = self.meter.short_term_lufs() as f64;
+        let desired_gain_db = if measured <= -144.0 {
+            0.0
+        } else {
+            (self.target_lufs - measured).clamp(-self.max_gain_db, self.max_gain_db)
+        };
+        let desired_gain = db_to_linear(desired_gain_db);
+
+        let n = left.len().min(right.len());
+        let mut limiter_active = false;
+        for i in 0..n {
+            // 逐样本一阶平滑：增益下降（信号变响，需要压低）用攻击系数，
+            // 增益回升（信号变轻，可以恢复）用释放系数
+            let coeff = if desired_gain < self.current_gain {
+                self.attack_coeff
+            } else {
+                self.release_coeff
+            };
+            self.current_gain = desired_gain + coeff * (self.current_gain - desired_gain);
+
+            let (l, l_limited) = soft_knee_limit(left[i] as f64 * self.current_gain);
+            let (r, r_limited) = soft_knee_limit(right[i] as f64 * self.current_gain);
+            limiter_active = limiter_active || l_limited || r_limited;
+
+            left[i] = l as f32;
+            right[i] = r as f32;
+        }
+        self.limiter_active = limiter_active;
+    }
+
+    /// 当前实际施加的增益（dB）
+    #[wasm_bindgen(getter)]
+    pub fn current_gain_db(&self) -> f32 {
+        linear_to_db(self.current_gain) as f32
+    }
+
+    /// 最近一次处理的块中限幅器是否介入压缩
+    #[wasm_bindgen(getter)]
+    pub fn limiter_active(&self) -> bool {
+        self.limiter_active
+    }
+
+    /// 重置内部响度测量与增益平滑状态
+    pub fn reset(&mut self) {
+        self.meter.reset_all();
+        self.current_gain = 1.0;
+        self.limiter_active = false;
+    }
+}
+
+// ============================================================
+// 有理多相重采样器 (Resampler)
+// ============================================================
+
+/// 重采样原型滤波器的 Kaiser 窗 β 参数
+const RESAMPLER_KAISER_BETA: f64 = 8.0;
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// 构建有理多相重采样所需的原型低通滤波器，并按 `l` 个相位拆分为多相子滤波器组
+///
+/// 原型滤波器总抽头数为 `l * taps_per_phase`，使用 Kaiser 窗 sinc 设计，
+/// 截止频率取输入/输出奈奎斯特频率中较低者（留出约 10% 过渡带余量），
+/// 系数乘以 `l` 以补偿零填充插值造成的通带增益损失
+fn build_resampler_polyphase(in_rate: f64, out_rate: f64, l: u32, taps_per_phase: usize) -> Vec<Vec<f64>> {
+    use std::f64::consts::PI;
+
+    let fs_poly = in_rate * l as f64; // 等价于 out_rate * m
+    let cutoff_hz = 0.5 * in_rate.min(out_rate) * 0.9;
+    let fc_norm = cutoff_hz / fs_poly;
+
+    let total_taps = l as usize * taps_per_phase;
+    let center = (total_taps - 1) as f64 / 2.0;
+    let i0_beta = bessel_i0(RESAMPLER_KAISER_BETA);
+
+    let mut proto = vec![0.0_f64; total_taps];
+    for (n, slot) in proto.iter_mut().enumerate() {
+        let x = n as f64 - center;
+        let sinc = if x.abs() < 1e-9 {
+            2.0 * fc_norm
+        } else {
+            (2.0 * PI * fc_norm * x).sin() / (PI * x)
+        };
+        let ratio = x / center;
+        let window = bessel_i0(RESAMPLER_KAISER_BETA * (1.0 - ratio * ratio).max(0.0).sqrt()) / i0_beta;
+        *slot = sinc * window;
+    }
+
+    let mut phases = vec![vec![0.0_f64; taps_per_phase]; l as usize];
+    for (p, phase) in phases.iter_mut().enumerate() {
+        for (k, coeff) in phase.iter_mut().enumerate() {
+            let idx = l as usize * k + p;
+            if idx < total_taps {
+                *coeff = proto[idx] * l as f64;
+            }
+        }
+    }
+    phases
+}
+
+/// 单声道的多相重采样延迟线状态
+struct ResamplerChannel {
+    /// 最近 `taps_per_phase` 个输入采样的历史（`history[0]` 为最新）
+    history: Vec<f64>,
+}
+
+impl ResamplerChannel {
+    fn new(taps_per_phase: usize) -> Self {
+        ResamplerChannel {
+            history: vec![0.0; taps_per_phase],
+        }
+    }
+
+    #[inline(always)]
+    fn push(&mut self, x: f64) {
+        for i in (1..self.history.len()).rev() {
+            self.history[i] = self.history[i - 1];
+        }
+        self.history[0] = x;
+    }
+
+    #[inline(always)]
+    fn compute(&self, phase_coeffs: &[f64]) -> f64 {
+        let mut acc = 0.0_f64;
+        for (coeff, hist) in phase_coeffs.iter().zip(self.history.iter()) {
+            acc += coeff * hist;
+        }
+        acc
+    }
+
+    fn reset(&mut self) {
+        for v in self.history.iter_mut() {
+            *v = 0.0;
+        }
+    }
+}
+
+/// 有理多相重采样器（polyphase resampler）
+///
+/// 将比例 `out_rate/in_rate` 约分为最简分数 `l/m`（例如 48000/44100 约分为 160/147），
+/// 概念上按 `l` 倍上采样（零值插入）、经单一 Kaiser 窗 sinc 低通（截止于输入/输出
+/// 奈奎斯特频率较低者）、再按 `m` 倍抽取；实现上用多相换向器（polyphase commutator）
+/// 只计算非零抽头：每产生一个输出样本，相位累加器加 `m`，溢出 `l` 的部分转换为
+/// 待消费的输入样本数，从而选中对应的 `l` 相位子滤波器组之一。
+/// 每声道维护独立的历史延迟线，使连续的 `process_block` 调用之间无缝衔接。
+#[wasm_bindgen]
+pub struct Resampler {
+    l: u32,
+    m: u32,
+    phases: Vec<Vec<f64>>,
+    phase: u32,
+    /// 上次调用结束时尚未消费完的相位溢出（跨 `process_block` 调用保留）
+    pending_advances: u32,
+    left: ResamplerChannel,
+    right: ResamplerChannel,
+}
+
+#[wasm_bindgen]
+impl Resampler {
+    /// 创建重采样器
+    ///
+    /// # 参数
+    /// - `in_rate` / `out_rate`: 输入/输出采样率（Hz）
+    /// - `quality_taps`: 每个多相子滤波器的抽头数，越大滤波器越陡峭但计算量越高
+    #[wasm_bindgen(constructor)]
+    pub fn new(in_rate: f32, out_rate: f32, quality_taps: usize) -> Resampler {
+        let in_rate_u = in_rate.round() as u32;
+        let out_rate_u = out_rate.round() as u32;
+        let g = gcd(in_rate_u, out_rate_u).max(1);
+        let l = out_rate_u / g;
+        let m = in_rate_u / g;
+        let taps_per_phase = quality_taps.max(1);
+
+        Resampler {
+            l,
+            m,
+            phases: build_resampler_polyphase(in_rate as f64, out_rate as f64, l, taps_per_phase),
+            phase: 0,
+            pending_advances: 0,
+            left: ResamplerChannel::new(taps_per_phase),
+            right: ResamplerChannel::new(taps_per_phase),
+        }
+    }
+
+    /// 立体声逐块重采样
+    ///
+    /// `out_left`/`out_right` 须预先分配足够容量（建议 `ceil(in_len * l / m) + quality_taps`）。
+    /// 返回实际写入的样本数；输入耗尽时尚未完成的相位推进会保留在内部状态中，由下一次调用的输入补齐。
+    /// 若输出缓冲区先写满，本块剩余的输入样本会被丢弃，不会保留到下一次调用。
+    pub fn process_block(
+        &mut self,
+        in_left: &[f32],
+        in_right: &[f32],
+        out_left: &mut [f32],
+        out_right: &mut [f32],
+    ) -> usize {
+        let in_len = in_left.len().min(in_right.len());
+        let out_capacity = out_left.len().min(out_right.len());
+        let mut in_idx = 0usize;
+        let mut out_idx = 0usize;
+
+        // 先补齐上次遗留的相位溢出
+        if self.pending_advances > 0 {
+            let available = (in_len - in_idx) as u32;
+            let consumed = self.pending_advances.min(available);
+            for _ in 0..consumed {
+                self.left.push(in_left[in_idx] as f64);
+                self.right.push(in_right[in_idx] as f64);
+                in_idx += 1;
+            }
+            self.pending_advances -= consumed;
+            if self.pending_advances > 0 {
+                return 0; // 输入仍不足以追上进度，本次调用不产生输出
+            }
+        }
+
+        while out_idx < out_capacity {
+            let phase_coeffs = &self.phases[self.phase as usize];
+            out_left[out_idx] = self.left.compute(phase_coeffs) as f32;
+            out_right[out_idx] = self.right.compute(phase_coeffs) as f32;
+            out_idx += 1;
+
+            self.phase += self.m;
+            let mut wraps = 0u32;
+            while self.phase >= self.l {
+                self.phase -= self.l;
+                wraps += 1;
+            }
+
+            let available = (in_len - in_idx) as u32;
+            let consumed = wraps.min(available);
+            for _ in 0..consumed {
+                self.left.push(in_left[in_idx] as f64);
+                self.right.push(in_right[in_idx] as f64);
+                in_idx += 1;
+            }
+            if consumed < wraps {
+                self.pending_advances = wraps - consumed;
+                break;
+            }
+        }
+
+        out_idx
+    }
+
+    /// 重置延迟线和相位状态
+    pub fn reset(&mut self) {
+        self.left.reset();
+        self.right.reset();
+        self.phase = 0;
+        self.pending_advances = 0;
+    }
+}
+
 // ============================================================
 // 内部单元测试（仅用于 cargo test，不编译到 WASM）
 // ============================================================
@@ -529,4 +1325,164 @@ mod tests {
             "1kHz 0dBFS sine short-term LUFS = {:.2}, expected roughly -3", short
         );
     }
+
+    /// 验证接近奈奎斯特频率的满幅正弦波，True Peak 读数明显高于采样峰值
+    ///
+    /// 采样峰值只能捕捉到实际采样点的幅度，而靠近 Nyquist 的正弦波在采样点之间
+    /// 可能出现更高的真实峰值，4× 过采样插值应当能够检测出这部分过冲
+    #[test]
+    fn test_true_peak_exceeds_sample_peak_near_nyquist() {
+        let mut meter = LufsMeter::new(48000.0);
+        let fs = 48000.0_f32;
+        let freq = 16000.0_f32; // 接近奈奎斯特频率，通带内但采样点间峰值明显高于采样点本身
+
+        for block in 0..40 {
+            let sine: Vec<f32> = (0..128)
+                .map(|i| {
+                    let t = (block * 128 + i) as f32 / fs;
+                    (2.0 * std::f32::consts::PI * freq * t).sin()
+                })
+                .collect();
+            meter.process_block(&sine, &sine);
+        }
+
+        let sample_peak = meter.peak_l_db();
+        let true_peak = meter.true_peak_l_db();
+        assert!(
+            true_peak > sample_peak + 0.1,
+            "True Peak ({:.3} dBFS) should read meaningfully above sample peak ({:.3} dBFS)",
+            true_peak, sample_peak
+        );
+    }
+
+    /// 验证一段稳定的 -23 LUFS 正弦波，Integrated Loudness 收敛到 ≈ -23 LUFS
+    ///
+    /// 1kHz 满幅正弦波的 K 权重响度约为 -3 LUFS（见 `test_full_scale_sine`），
+    /// 按线性幅度缩放 0.1 倍（-20 dB）即可得到约 -23 LUFS 的稳态信号
+    #[test]
+    fn test_integrated_lufs_steady_tone() {
+        let mut meter = LufsMeter::new(48000.0);
+        let fs = 48000.0_f32;
+        let freq = 1000.0_f32;
+        let amplitude = 0.1_f32; // ≈ -20 dB，把 ~-3 LUFS 的满幅音调移到 ~-23 LUFS
+
+        // 填充 5 秒稳态音频，足够让门限块积分收敛
+        for block in 0..1954 {
+            let sine: Vec<f32> = (0..128)
+                .map(|i| {
+                    let t = (block * 128 + i) as f32 / fs;
+                    amplitude * (2.0 * std::f32::consts::PI * freq * t).sin()
+                })
+                .collect();
+            meter.process_block(&sine, &sine);
+        }
+
+        let integrated = meter.integrated_lufs();
+        assert!(
+            integrated > -25.0 && integrated < -21.0,
+            "steady -23 LUFS tone integrated to {:.2}, expected roughly -23", integrated
+        );
+    }
+
+    /// 在 1kHz 处设置 +12dB 峰值提升，验证该频率正弦波的 RMS 明显高于直通
+    #[test]
+    fn test_equalizer_peaking_boost_raises_rms() {
+        let fs = 48000.0_f32;
+        let freq = 1000.0_f32;
+        let make_sine = || -> Vec<f32> {
+            (0..4800)
+                .map(|i| 0.1 * (2.0 * std::f32::consts::PI * freq * i as f32 / fs).sin())
+                .collect()
+        };
+
+        let mut dry_l = make_sine();
+        let mut dry_r = make_sine();
+        let dry_rms = (dry_l.iter().map(|x| (*x as f64).powi(2)).sum::<f64>() / dry_l.len() as f64).sqrt();
+
+        let mut eq = Equalizer::new(fs, 1);
+        eq.set_band(0, "peaking", freq, 12.0, 1.0);
+        eq.process_block(&mut dry_l, &mut dry_r);
+
+        let wet_rms = (dry_l.iter().map(|x| (*x as f64).powi(2)).sum::<f64>() / dry_l.len() as f64).sqrt();
+
+        assert!(
+            wet_rms > dry_rms * 2.5,
+            "boosted RMS {:.4} should be well above dry RMS {:.4}", wet_rms, dry_rms
+        );
+    }
+
+    /// 一段很轻（约 -40 LUFS）的稳态音调经过归一化后，增益应被推高以接近目标响度
+    #[test]
+    fn test_loudness_normalizer_boosts_quiet_signal() {
+        let fs = 48000.0_f32;
+        let freq = 1000.0_f32;
+        let amplitude = 0.01_f32; // 满幅 -40dB，约 -43 LUFS
+
+        let mut norm = LoudnessNormalizer::new(fs, -14.0);
+
+        // 先跑几秒让短期窗口和增益平滑收敛到稳态
+        for block in 0..500 {
+            let sine: Vec<f32> = (0..256)
+                .map(|i| {
+                    let t = (block * 256 + i) as f32 / fs;
+                    amplitude * (2.0 * std::f32::consts::PI * freq * t).sin()
+                })
+                .collect();
+            let mut l = sine.clone();
+            let mut r = sine;
+            norm.process_block(&mut l, &mut r);
+        }
+
+        assert!(
+            norm.current_gain_db() > 15.0,
+            "expected substantial gain boost for a quiet signal, got {:.2} dB", norm.current_gain_db()
+        );
+        assert!(!norm.limiter_active());
+    }
+
+    /// 48kHz→44.1kHz 转换后，1kHz 正弦波的频率和 RMS 应在容差范围内保持不变
+    #[test]
+    fn test_resampler_preserves_frequency_and_rms_48k_to_44_1k() {
+        let in_rate = 48000.0_f32;
+        let out_rate = 44100.0_f32;
+        let freq = 1000.0_f32;
+        let amplitude = 0.5_f32;
+        let in_len = 96000; // 2 秒
+
+        let in_l: Vec<f32> = (0..in_len)
+            .map(|i| amplitude * (2.0 * std::f32::consts::PI * freq * i as f32 / in_rate).sin())
+            .collect();
+        let in_r = in_l.clone();
+
+        let mut resampler = Resampler::new(in_rate, out_rate, 32);
+        let out_capacity = (in_len as f64 * out_rate as f64 / in_rate as f64).ceil() as usize + 64;
+        let mut out_l = vec![0.0_f32; out_capacity];
+        let mut out_r = vec![0.0_f32; out_capacity];
+        let produced = resampler.process_block(&in_l, &in_r, &mut out_l, &mut out_r);
+        out_l.truncate(produced);
+
+        // 跳过滤波器建立瞬态，只分析稳态部分
+        let steady = &out_l[2000..produced - 200];
+
+        let rms = (steady.iter().map(|x| (*x as f64).powi(2)).sum::<f64>() / steady.len() as f64).sqrt();
+        let expected_rms = amplitude as f64 / std::f64::consts::SQRT_2;
+        assert!(
+            (rms - expected_rms).abs() < expected_rms * 0.1,
+            "resampled RMS {:.4} should be close to expected {:.4}", rms, expected_rms
+        );
+
+        // 用过零点间隔估计频率
+        let mut crossings = 0usize;
+        for w in steady.windows(2) {
+            if w[0] <= 0.0 && w[1] > 0.0 {
+                crossings += 1;
+            }
+        }
+        let duration_s = steady.len() as f64 / out_rate as f64;
+        let estimated_freq = crossings as f64 / duration_s;
+        assert!(
+            (estimated_freq - freq as f64).abs() < 20.0,
+            "resampled frequency {:.2}Hz should be close to {:.2}Hz", estimated_freq, freq
+        );
+    }
 }